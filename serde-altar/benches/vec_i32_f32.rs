@@ -0,0 +1,27 @@
+//! Compare decoding a large list of `f32`s all at once through `VecI32F32` with decoding it element by element through `VecI32`.
+
+use bencher::{benchmark_group, benchmark_main, black_box, Bencher};
+use serde_altar::{from_bytes, to_bytes, VecI32, VecI32F32};
+
+/// The amount of floats in the decoded list.
+const COUNT: usize = 100_000;
+
+fn floats() -> Vec<u8> {
+    let values: Vec<f32> = (0..COUNT).map(|index| index as f32 * 0.25).collect();
+    to_bytes(VecI32F32(values)).unwrap()
+}
+
+fn bulk(bench: &mut Bencher) {
+    let bytes = floats();
+    bench.bytes = bytes.len() as u64;
+    bench.iter(|| from_bytes::<VecI32F32>(black_box(&bytes)).unwrap());
+}
+
+fn element_wise(bench: &mut Bencher) {
+    let bytes = floats();
+    bench.bytes = bytes.len() as u64;
+    bench.iter(|| from_bytes::<VecI32<f32>>(black_box(&bytes)).unwrap());
+}
+
+benchmark_group!(benches, bulk, element_wise);
+benchmark_main!(benches);
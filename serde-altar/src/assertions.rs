@@ -0,0 +1,117 @@
+//! Compile-time checks on the auto traits of the public types.
+//!
+//! (De)serializers are [Send] and [Sync] whenever their reader or writer is, and every other public type is always both, so that multiple files can be processed in parallel.
+
+use crate::*;
+
+/// Fail compilation if `T` can't be sent or shared across threads.
+fn assert_send_sync<T: Send + Sync>() {}
+
+const _: fn() = || {
+    assert_send_sync::<Error>();
+    assert_send_sync::<WriteSerializer<Vec<u8>>>();
+    assert_send_sync::<ReadDeserializer<'static, std::io::Cursor<Vec<u8>>>>();
+    assert_send_sync::<SliceDeserializer<'static>>();
+    assert_send_sync::<Checkpoint<'static>>();
+    assert_send_sync::<StringEncoding>();
+    assert_send_sync::<SectionValidator<'static, 'static, std::io::Cursor<Vec<u8>>>>();
+    assert_send_sync::<ChecksummedReader<std::io::Cursor<Vec<u8>>>>();
+    assert_send_sync::<ChecksummedWriter<Vec<u8>>>();
+    assert_send_sync::<ChecksumAlgorithm>();
+
+    assert_send_sync::<VecI16Flags>();
+    assert_send_sync::<VecULEB128<u8>>();
+    assert_send_sync::<VecSLEB128<u8>>();
+    assert_send_sync::<RleVec<u8, u16>>();
+    assert_send_sync::<MapULEB128<u8, u8>>();
+    assert_send_sync::<VecI16<u8>>();
+    assert_send_sync::<VecI32<u8>>();
+    assert_send_sync::<VecU8<u8>>();
+    assert_send_sync::<VecU32<u8>>();
+    assert_send_sync::<VecI64<u8>>();
+    assert_send_sync::<VecI32F32>();
+
+    assert_send_sync::<Leb128U64>();
+    assert_send_sync::<VersionedU8>();
+    assert_send_sync::<BoolGrid2D>();
+    assert_send_sync::<BlobList>();
+    assert_send_sync::<Tlv>();
+    assert_send_sync::<RangeI32>();
+    assert_send_sync::<VersionedArray<4, 195>>();
+    assert_send_sync::<BitmaskU64<64>>();
+    assert_send_sync::<Color>();
+    assert_send_sync::<OptionalColor>();
+    assert_send_sync::<FixedPoint<100>>();
+    assert_send_sync::<NullableStringI32>();
+    assert_send_sync::<DeltaF32Series<100>>();
+    assert_send_sync::<PackedNBit<4>>();
+    assert_send_sync::<TrailingOption<i32>>();
+    assert_send_sync::<Rect>();
+    assert_send_sync::<DateTimeKind>();
+    assert_send_sync::<DateTime>();
+    assert_send_sync::<CharMap>();
+    assert_send_sync::<Vector2>();
+    assert_send_sync::<DeltaPosList>();
+    assert_send_sync::<OptionBool<i32>>();
+    assert_send_sync::<ExtraHeader>();
+    assert_send_sync::<BoolArray<5>>();
+    assert_send_sync::<Guid>();
+
+    assert_send_sync::<BossFlags>();
+    assert_send_sync::<OreTiers>();
+    assert_send_sync::<PartyData>();
+    assert_send_sync::<TravelingMerchantInventory>();
+    assert_send_sync::<MoonData>();
+    assert_send_sync::<InvasionData>();
+    assert_send_sync::<GenInfo>();
+    assert_send_sync::<TileShape>();
+    assert_send_sync::<TownNpc>();
+    assert_send_sync::<CreativePower>();
+    assert_send_sync::<CreativePowers>();
+    assert_send_sync::<TileCounts<700>>();
+    assert_send_sync::<TimeState>();
+    assert_send_sync::<LogicSensor>();
+    assert_send_sync::<WeightedPlate>();
+    assert_send_sync::<WorldNames>();
+    assert_send_sync::<Backgrounds>();
+    assert_send_sync::<Difficulty>();
+    assert_send_sync::<LayerDepths>();
+    assert_send_sync::<NamedKillCounts>();
+    assert_send_sync::<WorldFooter>();
+    assert_send_sync::<PaintColor>();
+    assert_send_sync::<ItemStack>();
+    assert_send_sync::<ItemFrame>();
+    assert_send_sync::<WeaponRack>();
+    assert_send_sync::<Sandstorm>();
+    assert_send_sync::<LanternNight>();
+    assert_send_sync::<EventTimers>();
+    assert_send_sync::<ShimmerPosition>();
+    assert_send_sync::<ShimmerData>();
+
+    assert_send_sync::<MapTile>();
+    assert_send_sync::<MapHeader>();
+    assert_send_sync::<MapTileColor>();
+};
+
+#[cfg(test)]
+mod tests {
+    use crate::*;
+
+    #[test]
+    fn decode_across_threads() {
+        let files: Vec<Vec<u8>> = (0..4).map(|index| to_bytes(VecI32(vec![index; 3])).unwrap()).collect();
+        let handles: Vec<_> = files.into_iter().map(|bytes| std::thread::spawn(move || {
+            let mut de = ReadDeserializer::owned(std::io::Cursor::new(bytes));
+            <VecI32<i32> as Deserialize<i32>>::deserialize(&mut de).map(|vec| vec.0)
+        })).collect();
+        for (index, handle) in handles.into_iter().enumerate() {
+            assert_eq!(handle.join().unwrap().unwrap(), vec![index as i32; 3]);
+        }
+    }
+
+    #[test]
+    fn errors_cross_threads() {
+        let error = std::thread::spawn(|| from_bytes::<i32>(&[0, 0]).unwrap_err()).join().unwrap();
+        assert_eq!(error, Error::At { position: 0, error: Box::new(Error::Eof) });
+    }
+}
@@ -0,0 +1,188 @@
+/// Algorithm used to compute the checksum appended to a file by some community tools.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChecksumAlgorithm {
+    /// CRC-32 (IEEE 802.3), stored as a little-endian [u32].
+    Crc32,
+}
+
+impl ChecksumAlgorithm {
+    /// The amount of bytes the checksum takes in the file.
+    pub fn size(self) -> usize {
+        match self {
+            ChecksumAlgorithm::Crc32 => 4,
+        }
+    }
+}
+
+/// Update a running CRC-32 with the given `bytes`.
+///
+/// The running value starts at `0`.
+pub(crate) fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    let mut crc = !crc;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            // 0xEDB88320 is the reversed IEEE polynomial.
+            crc = match crc & 1 {
+                0 => crc >> 1,
+                _ => (crc >> 1) ^ 0xEDB8_8320,
+            };
+        }
+    }
+    !crc
+}
+
+/// [std::io::Read] adapter computing a running checksum of all the bytes read through it.
+pub struct ChecksummedReader<R> where R: std::io::Read {
+    reader: R,
+
+    /// The CRC-32 of the bytes read so far.
+    crc32: u32,
+}
+
+impl<R> ChecksummedReader<R> where R: std::io::Read {
+    /// Create a new checksummed reader reading from the given `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            crc32: 0,
+        }
+    }
+
+    /// The checksum of the bytes read so far, computed with the given `algo`.
+    pub fn checksum(&self, algo: ChecksumAlgorithm) -> u32 {
+        match algo {
+            ChecksumAlgorithm::Crc32 => self.crc32,
+        }
+    }
+
+    /// Read the checksum trailing the file, and compare it to the one of the bytes read so far.
+    ///
+    /// Fails with [crate::Error::ChecksumMismatch] if they differ.
+    pub fn verify_trailing_checksum(&mut self, algo: ChecksumAlgorithm) -> crate::Result<()> {
+        let actual = self.checksum(algo);
+        let expected = match algo {
+            ChecksumAlgorithm::Crc32 => {
+                let mut buf = [0; 4];
+                self.reader.read_exact(&mut buf)?;
+                u32::from_le_bytes(buf)
+            },
+        };
+        match expected == actual {
+            true => Ok(()),
+            false => Err(crate::Error::ChecksumMismatch { expected, actual }),
+        }
+    }
+
+    /// Get back the wrapped reader.
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+impl<R> std::io::Read for ChecksummedReader<R> where R: std::io::Read {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        self.crc32 = crc32_update(self.crc32, &buf[..size]);
+        Ok(size)
+    }
+}
+
+/// [std::io::Write] adapter computing a running checksum of all the bytes written through it, so that it can be appended to the file.
+pub struct ChecksummedWriter<W> where W: std::io::Write {
+    writer: W,
+
+    /// The CRC-32 of the bytes written so far.
+    crc32: u32,
+}
+
+impl<W> ChecksummedWriter<W> where W: std::io::Write {
+    /// Create a new checksummed writer writing to the given `writer`.
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            crc32: 0,
+        }
+    }
+
+    /// The checksum of the bytes written so far, computed with the given `algo`.
+    pub fn checksum(&self, algo: ChecksumAlgorithm) -> u32 {
+        match algo {
+            ChecksumAlgorithm::Crc32 => self.crc32,
+        }
+    }
+
+    /// Append the checksum of the bytes written so far to the file, in the format read by [ChecksummedReader::verify_trailing_checksum].
+    pub fn write_trailing_checksum(&mut self, algo: ChecksumAlgorithm) -> crate::Result<()> {
+        let checksum = self.checksum(algo);
+        match algo {
+            ChecksumAlgorithm::Crc32 => self.writer.write_all(&checksum.to_le_bytes())?,
+        };
+        Ok(())
+    }
+
+    /// Get back the wrapped writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> std::io::Write for ChecksummedWriter<W> where W: std::io::Write {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let size = self.writer.write(buf)?;
+        self.crc32 = crc32_update(self.crc32, &buf[..size]);
+        Ok(size)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn crc32_check_value() {
+        assert_eq!(crc32_update(0, b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32_update(crc32_update(0, b"1234"), b"56789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn checksum_roundtrip() {
+        let value = crate::VecI32(vec![1_i32, -2, 3]);
+        let mut writer = crate::to_writer(ChecksummedWriter::new(vec![]), value).unwrap();
+        let checksum = writer.checksum(ChecksumAlgorithm::Crc32);
+        writer.write_trailing_checksum(ChecksumAlgorithm::Crc32).unwrap();
+        let bytes = writer.into_inner();
+        assert_eq!(bytes.len(), 4 + 3 * 4 + ChecksumAlgorithm::Crc32.size());
+        assert_eq!(bytes[bytes.len() - 4..], checksum.to_le_bytes());
+
+        let mut reader = ChecksummedReader::new(&bytes[..]);
+        let mut body = [0; 16];
+        reader.read_exact(&mut body).unwrap();
+        assert_eq!(reader.checksum(ChecksumAlgorithm::Crc32), checksum);
+        reader.verify_trailing_checksum(ChecksumAlgorithm::Crc32).unwrap();
+
+        let mut de = crate::ReadDeserializer::owned(ChecksummedReader::new(&bytes[..]));
+        let decoded = <crate::VecI32<i32> as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded.0, vec![1, -2, 3]);
+    }
+
+    #[test]
+    fn checksum_mismatch() {
+        let mut writer = ChecksummedWriter::new(vec![]);
+        std::io::Write::write_all(&mut writer, b"123456789").unwrap();
+        writer.write_trailing_checksum(ChecksumAlgorithm::Crc32).unwrap();
+        let mut bytes = writer.into_inner();
+        bytes[0] = b'0';
+
+        let mut reader = ChecksummedReader::new(&bytes[..]);
+        let mut body = [0; 9];
+        reader.read_exact(&mut body).unwrap();
+        let actual = crc32_update(0, b"023456789");
+        assert_eq!(reader.verify_trailing_checksum(ChecksumAlgorithm::Crc32).err(), Some(crate::Error::ChecksumMismatch { expected: 0xCBF4_3926, actual }));
+    }
+}
@@ -10,11 +10,97 @@ impl<'a, 'de, R> serde::de::SeqAccess<'de> for ValueSized<'a, 'de, R> where R: s
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> where T: serde::de::DeserializeSeed<'de> {
         match self.size {
             0 => Ok(None),
-            _ => seed.deserialize(&mut *self.de).map(Some),
+            _ => {
+                self.size -= 1;
+                seed.deserialize(&mut *self.de).map(Some)
+            },
         }
     }
 
     fn size_hint(&self) -> Option<usize> {
-        Some(self.size)
+        // The size comes from the file, so it can't be trusted for preallocation.
+        Some(self.size.min(crate::de::deserializer::MAX_PREALLOC))
+    }
+}
+
+/// Map having a known number of entries inside, each stored as its key followed by its value.
+pub struct EntrySized<'a, 'de: 'a, R> where R: std::io::Read {
+    pub de: &'a mut crate::de::ReadDeserializer<'de, R>,
+    pub size: usize,
+}
+
+impl<'a, 'de, R> serde::de::MapAccess<'de> for EntrySized<'a, 'de, R> where R: std::io::Read {
+    type Error = crate::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> where K: serde::de::DeserializeSeed<'de> {
+        match self.size {
+            0 => Ok(None),
+            _ => {
+                self.size -= 1;
+                seed.deserialize(&mut *self.de).map(Some)
+            },
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error> where V: serde::de::DeserializeSeed<'de> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // The size comes from the file, so it can't be trusted for preallocation.
+        Some(self.size.min(crate::de::deserializer::MAX_PREALLOC))
+    }
+}
+
+/// Sequence yielding the file version before the values read from the deserializer, with no known length.
+pub struct Versioned<'a, 'de: 'a, R> where R: std::io::Read {
+    pub de: &'a mut crate::de::ReadDeserializer<'de, R>,
+    pub version: Option<i32>,
+}
+
+impl<'a, 'de, R> serde::de::SeqAccess<'de> for Versioned<'a, 'de, R> where R: std::io::Read {
+    type Error = crate::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error> where T: serde::de::DeserializeSeed<'de> {
+        match self.version.take() {
+            Some(version) => seed.deserialize(serde::de::IntoDeserializer::into_deserializer(version)).map(Some),
+            None => seed.deserialize(&mut *self.de).map(Some),
+        }
+    }
+}
+
+/// `enum` whose variant is selected by a discriminant which has already been read, followed by the fields of the variant.
+pub struct Discriminated<'a, 'de: 'a, R, D> where R: std::io::Read {
+    pub de: &'a mut crate::de::ReadDeserializer<'de, R>,
+    pub discriminant: D,
+}
+
+impl<'a, 'de, R, D> serde::de::EnumAccess<'de> for Discriminated<'a, 'de, R, D> where R: std::io::Read, D: serde::de::IntoDeserializer<'de, crate::Error> + Copy {
+    type Error = crate::Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> where V: serde::de::DeserializeSeed<'de> {
+        let variant = seed.deserialize(self.discriminant.into_deserializer())?;
+        Ok((variant, self))
+    }
+}
+
+impl<'a, 'de, R, D> serde::de::VariantAccess<'de> for Discriminated<'a, 'de, R, D> where R: std::io::Read {
+    type Error = crate::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error> where T: serde::de::DeserializeSeed<'de> {
+        seed.deserialize(&mut *self.de)
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        visitor.visit_seq(ValueSized { de: self.de, size: len })
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        visitor.visit_seq(ValueSized { de: self.de, size: fields.len() })
     }
 }
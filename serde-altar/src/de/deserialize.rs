@@ -1,9 +1,44 @@
 use std::marker::PhantomData;
-use serde::de::Error;
 use crate::VecI16Flags;
+use crate::VecI32F32;
 use crate::VecULEB128;
-use crate::VecI16;
-use crate::VecI32;
+use crate::VecSLEB128;
+use crate::RleVec;
+use crate::RleCount;
+use crate::MapULEB128;
+use crate::BossFlags;
+use crate::Leb128U64;
+use crate::VersionedU8;
+use crate::BoolGrid2D;
+use crate::BlobList;
+use crate::ShopInventory;
+use crate::Tlv;
+use crate::RangeI32;
+use crate::VersionedArray;
+use crate::BitmaskU64;
+use crate::TileShape;
+use crate::OptionalColor;
+use crate::TrailingOption;
+use crate::OptionBool;
+use crate::CharMap;
+use crate::Difficulty;
+use crate::DeltaPosList;
+use crate::CreativePowers;
+use crate::FixedPoint;
+use crate::DeltaF32Series;
+use crate::PackedNBit;
+use crate::TileCounts;
+use crate::BoolArray;
+use crate::Backgrounds;
+use crate::NullableStringI32;
+use crate::MapHeader;
+use crate::ExtraHeader;
+use crate::NamedKillCounts;
+use crate::PaintColor;
+use crate::Guid;
+use crate::EventTimers;
+use crate::MapTileColor;
+use crate::ShimmerData;
 
 
 /// Custom deserialize trait with support for the weird Terraria array serialization.
@@ -11,9 +46,16 @@ pub trait Deserialize<'de, T> : serde::de::Deserialize<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, T: crate::de::Deserialize<'de, T>;
 }
 
+/// Values supported by the base serde data model deserialize as themselves.
+impl<'de, T> Deserialize<'de, T> for T where T: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de> {
+        serde::de::Deserialize::deserialize(deserializer)
+    }
+}
+
 impl<'de> serde::Deserialize<'de> for VecI16Flags {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
-        Err(D::Error::custom("Cannot deserialize VecI16Flags with the serde Deserializer"))
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(2, crate::de::visitor::VecI16FlagsVisitor)
     }
 }
 
@@ -23,38 +65,273 @@ impl<'de> Deserialize<'de, bool> for VecI16Flags {
     }
 }
 
-impl<'de, T> serde::Deserialize<'de> for VecULEB128<T> {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
-        Err(D::Error::custom("Cannot deserialize VecULEB128 with the serde Deserializer"))
+impl<'de, T> serde::Deserialize<'de> for VecULEB128<T> where T: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // Sequences of bytes can be read all at once instead of one element at a time.
+        let name = match crate::de::probe::ByteProbe::accepts::<T>() {
+            true => crate::de::BYTES_ULEB128,
+            false => crate::de::SEQ_ULEB128,
+        };
+        deserializer.deserialize_newtype_struct(name, crate::de::visitor::VecULEB128Visitor::<T>(PhantomData))
     }
 }
 
-impl<'de, T> Deserialize<'de, T> for VecULEB128<T> {
+impl<'de, T> Deserialize<'de, T> for VecULEB128<T> where T: serde::de::Deserialize<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, T: crate::de::Deserialize<'de, T> {
         deserializer.deserialize_vec_uleb128(crate::de::visitor::VecULEB128Visitor::<T>(PhantomData))
     }
 }
 
-impl<'de, T> serde::Deserialize<'de> for VecI16<T> {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
-        Err(D::Error::custom("Cannot deserialize VecI16 with the serde Deserializer"))
+impl<'de, T> serde::Deserialize<'de> for VecSLEB128<T> where T: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::SEQ_SLEB128, crate::de::visitor::VecSLEB128Visitor::<T>(PhantomData))
     }
 }
 
-impl<'de, T> Deserialize<'de, T> for VecI16<T> {
+impl<'de, T> Deserialize<'de, T> for VecSLEB128<T> where T: serde::de::Deserialize<'de> {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, T: crate::de::Deserialize<'de, T> {
-        deserializer.deserialize_vec_i16(crate::de::visitor::VecI16Visitor::<T>(PhantomData))
+        deserializer.deserialize_vec_sleb128(crate::de::visitor::VecSLEB128Visitor::<T>(PhantomData))
+    }
+}
+
+impl<'de, K, V> serde::Deserialize<'de> for MapULEB128<K, V> where K: serde::de::Deserialize<'de>, V: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::MAP_ULEB128, crate::de::visitor::MapULEB128Visitor::<K, V>(PhantomData))
+    }
+}
+
+impl<'de, K, V> Deserialize<'de, K> for MapULEB128<K, V> where K: serde::de::Deserialize<'de>, V: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, K: crate::de::Deserialize<'de, K> {
+        deserializer.deserialize_map_uleb128(crate::de::visitor::MapULEB128Visitor::<K, V>(PhantomData))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BossFlags {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::VERSIONED, crate::de::visitor::BossFlagsVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Leb128U64 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::ULEB128, crate::de::visitor::Leb128U64Visitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VersionedU8 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::TRAILING_U8, crate::de::visitor::VersionedU8Visitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for VecI32F32 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The amount of bytes to read is only known once the length is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::VecI32F32Visitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BoolGrid2D {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(3, crate::de::visitor::BoolGrid2DVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for BlobList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::SEQ_I32, crate::de::visitor::BlobListVisitor)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for ShopInventory<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, N * 4, crate::de::visitor::ShopInventoryVisitor::<N>)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Tlv {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(2, crate::de::visitor::TlvVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for RangeI32 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::RANGE_I32, 2, crate::de::visitor::RangeI32Visitor)
+    }
+}
+
+impl<'de, const N: usize, const COUNTED_SINCE: i32> serde::Deserialize<'de> for VersionedArray<N, COUNTED_SINCE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::VERSIONED, crate::de::visitor::VersionedArrayVisitor::<N, COUNTED_SINCE>)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for BitmaskU64<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_u64(crate::de::visitor::BitmaskU64Visitor::<N>)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for TileShape {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_u8(crate::de::visitor::TileShapeVisitor)
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for OptionBool<T> where T: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The value is only read if the presence flag is set.
+        deserializer.deserialize_tuple(2, crate::de::visitor::OptionBoolVisitor::<T>(std::marker::PhantomData))
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for TrailingOption<T> where T: serde::de::Deserialize<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(2, crate::de::visitor::TrailingOptionVisitor::<T>(std::marker::PhantomData))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for OptionalColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The color is only read if the presence flag is set.
+        deserializer.deserialize_tuple(2, crate::de::visitor::OptionalColorVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CreativePowers {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The length of the list is only known once its end is reached.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::CreativePowersVisitor)
+    }
+}
+
+impl<'de, const SCALE: i64> serde::Deserialize<'de> for FixedPoint<SCALE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_i32(crate::de::visitor::FixedPointVisitor::<SCALE>)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for TileCounts<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, N * 4, crate::de::visitor::TileCountsVisitor::<N>)
+    }
+}
+
+impl<'de, const N: usize> serde::Deserialize<'de> for BoolArray<N> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(N, crate::de::visitor::BoolArrayVisitor::<N>)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Backgrounds {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, crate::world::BACKGROUNDS_LEN * 4, crate::de::visitor::BackgroundsVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for NullableStringI32 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The bytes are only read if the length is not null.
+        deserializer.deserialize_newtype_struct(crate::de::NULLABLE_STRING_I32, crate::de::visitor::NullableStringI32Visitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MapHeader {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple(5, crate::de::visitor::MapHeaderVisitor)
+    }
+}
+
+impl<'de, const SCALE: i64> serde::Deserialize<'de> for DeltaF32Series<SCALE> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The length of the series is only known once it is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::DeltaF32SeriesVisitor::<SCALE>)
+    }
+}
+
+impl<'de, const BITS: u8> serde::Deserialize<'de> for PackedNBit<BITS> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The amount of packed bytes is only known once the length is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::PackedNBitVisitor::<BITS>)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for CharMap {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The length of the table is only known once it is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::CharMapVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Difficulty {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::VERSIONED, crate::de::visitor::DifficultyVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for DeltaPosList {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The length of the list is only known once it is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::DeltaPosListVisitor)
     }
 }
 
-impl<'de, T> serde::Deserialize<'de> for VecI32<T> {
-    fn deserialize<D>(_deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
-        Err(D::Error::custom("Cannot deserialize VecI32 with the serde Deserializer"))
+impl<'de, T, C> serde::Deserialize<'de> for RleVec<T, C> where T: serde::de::Deserialize<'de> + Clone, C: RleCount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The amount of runs is only known once they are all read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::RleVecVisitor::<T, C>(PhantomData))
     }
 }
 
-impl<'de, T> Deserialize<'de, T> for VecI32<T> {
+impl<'de, T, C> Deserialize<'de, T> for RleVec<T, C> where T: serde::de::Deserialize<'de> + Clone, C: RleCount {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, T: crate::de::Deserialize<'de, T> {
-        deserializer.deserialize_vec_i32(crate::de::visitor::VecI32Visitor::<T>(PhantomData))
+        deserializer.deserialize_rle(crate::de::visitor::RleVecVisitor::<T, C>(PhantomData))
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for NamedKillCounts {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // The length of the list is only known once it is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::NamedKillCountsVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for PaintColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_u8(crate::de::visitor::PaintColorVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Guid {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, 16, crate::de::visitor::GuidVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for EventTimers {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::VERSIONED, crate::de::visitor::EventTimersVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for MapTileColor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        // Whether the light level is stored is only known once the header is read.
+        deserializer.deserialize_tuple(usize::MAX, crate::de::visitor::MapTileColorVisitor)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ShimmerData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::VERSIONED, crate::de::visitor::ShimmerDataVisitor)
+    }
+}
+
+impl ExtraHeader {
+    /// Read exactly `len` bytes from the given deserializer, without interpreting them.
+    pub fn decode<'de, D>(deserializer: D, len: usize) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+        let bytes = serde::de::DeserializeSeed::deserialize(crate::de::visitor::BytesVisitor { len }, deserializer)?;
+        Ok(ExtraHeader { bytes })
     }
 }
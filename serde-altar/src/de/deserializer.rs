@@ -1,4 +1,5 @@
 use crate::de::Visitor;
+use crate::MapTile;
 
 /// Custom deserializer trait with support for the weird Terraria array serialization.
 pub trait Deserializer<'de> : serde::de::Deserializer<'de> {
@@ -14,35 +15,631 @@ pub trait Deserializer<'de> : serde::de::Deserializer<'de> {
 
     /// Hint that the `Deserialize` type is expecting a sequence of values, prefixed with the sequence size as an ULEB128.
     fn deserialize_vec_uleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of values, prefixed with the sequence size as an SLEB128.
+    fn deserialize_vec_sleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of runs of values, prefixed with the total amount of values as an [i32].
+    fn deserialize_rle<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a table of keys and values, prefixed with the table size as an ULEB128.
+    fn deserialize_map_uleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of values, prefixed with the sequence size as an [u8].
+    fn deserialize_vec_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of values, prefixed with the sequence size as an [u32].
+    fn deserialize_vec_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting a sequence of values, prefixed with the sequence size as an [i64].
+    fn deserialize_vec_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: crate::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting an `enum`, whose variant is selected by a leading [u8] discriminant.
+    fn deserialize_enum_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de>;
+
+    /// Hint that the `Deserialize` type is expecting an `enum`, whose variant is selected by a leading [i32] discriminant.
+    ///
+    /// Used for `enum`s renamed to `"$serde_altar::EnumI32"`; negative discriminants are rejected.
+    fn deserialize_enum_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de>;
+}
+
+
+/// The default maximum amount of bytes a [ReadDeserializer] allocates at once for a single value.
+pub const DEFAULT_MAX_ALLOC: usize = 16 * 1024 * 1024;
+
+/// The maximum amount of elements allocated in advance for a length read from the file, before any of them is actually read.
+///
+/// Anything past this is allocated as the elements are read, so that a bogus length can't cause a huge allocation by itself.
+pub(crate) const MAX_PREALLOC: usize = 4096;
+
+/// How the bytes of the strings read by a [ReadDeserializer] are turned into text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StringEncoding {
+    /// Strings must be valid UTF-8, or [crate::Error::Utf8] is returned.
+    Utf8,
+
+    /// Invalid UTF-8 sequences are replaced with [std::char::REPLACEMENT_CHARACTER].
+    Utf8Lossy,
+
+    /// Each byte is a character on its own, as in the strings of some older files.
+    Latin1,
+}
+
+impl StringEncoding {
+    /// Turn the given `bytes` into text, borrowing them if possible.
+    pub(crate) fn decode(self, bytes: &[u8]) -> crate::Result<std::borrow::Cow<'_, str>> {
+        match self {
+            StringEncoding::Utf8 => std::str::from_utf8(bytes).map(std::borrow::Cow::Borrowed).map_err(|err| crate::Error::utf8(bytes, err)),
+            StringEncoding::Utf8Lossy => Ok(String::from_utf8_lossy(bytes)),
+            StringEncoding::Latin1 => Ok(std::borrow::Cow::Owned(bytes.iter().map(|byte| char::from(*byte)).collect())),
+        }
+    }
 }
 
+/// How the size of a block read by [ReadDeserializer::deserialize_sized_block] is stored before its contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LenKind {
+    /// An [i16], read according to [ReadDeserializer::treat_i16_len_as_unsigned].
+    I16,
+
+    /// An [i32].
+    I32,
+
+    /// An [u32].
+    U32,
+
+    /// An [i64].
+    I64,
+
+    /// An ULEB128.
+    Uleb128,
+}
 
 /// `Read`-based deserializer for Terraria world files.
 pub struct ReadDeserializer<'de, R> where R: std::io::Read {
-    pub(crate) reader: &'de mut R
+    pub(crate) reader: Source<'de, R>,
+
+    /// Whether [i16] lengths above [i16::MAX] should be read as [u16] instead of being rejected.
+    pub(crate) treat_i16_len_as_unsigned: bool,
+
+    /// The version of the file being read, used by values whose layout changed across versions.
+    pub(crate) version: Option<i32>,
+
+    /// The amount of bytes read from the `reader` so far.
+    pub(crate) position: u64,
+
+    /// The maximum amount of bytes that can be allocated at once for a single value.
+    pub(crate) max_alloc: usize,
+
+    /// Whether [crate::RangeI32]s with `start` greater than `end` should be rejected.
+    pub(crate) reject_inverted_ranges: bool,
+
+    /// How the bytes of strings are turned into text.
+    pub(crate) string_encoding: StringEncoding,
+
+    /// Buffer strings are read into, reused across strings to avoid allocating each of them.
+    pub(crate) scratch: Vec<u8>,
+
+    /// The absolute offset in the `reader` which is treated as position `0`.
+    pub(crate) base: u64,
+
+    /// Function taking bytes directly out of the `reader`, available only if it is an in-memory slice.
+    pub(crate) borrow: Option<BorrowFn<'de, R>>,
+
+    /// Function telling how many bytes the `reader` has left, available only if it is an in-memory slice.
+    pub(crate) available: Option<fn(&R) -> usize>,
+}
+
+/// The `reader` of a [ReadDeserializer], which is either borrowed from the caller or owned by the deserializer itself.
+pub(crate) enum Source<'de, R> where R: std::io::Read {
+    Borrowed(&'de mut R),
+    Owned(R),
+}
+
+impl<'de, R> Source<'de, R> where R: std::io::Read {
+    /// The reader, however it is stored.
+    pub(crate) fn get_mut(&mut self) -> &mut R {
+        match self {
+            Source::Borrowed(reader) => reader,
+            Source::Owned(reader) => reader,
+        }
+    }
+}
+
+impl<'de, R> std::io::Read for Source<'de, R> where R: std::io::Read {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.get_mut().read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> std::io::Result<()> {
+        self.get_mut().read_exact(buf)
+    }
+}
+
+/// Function taking `len` bytes out of a reader of type `R`, borrowing them for `'de`.
+pub(crate) type BorrowFn<'de, R> = fn(&mut R, usize) -> crate::Result<&'de [u8]>;
+
+/// [ReadDeserializer] reading from an in-memory slice, advancing it as an internal cursor.
+///
+/// Strings are borrowed from the slice when the value being deserialized allows it, instead of being copied into a new allocation.
+pub type SliceDeserializer<'de> = ReadDeserializer<'de, &'de [u8]>;
+
+/// Take the first `len` bytes out of `input`, without copying them.
+fn split_borrowed<'de>(input: &mut &'de [u8], len: usize) -> crate::Result<&'de [u8]> {
+    if input.len() < len {
+        return Err(crate::Error::Eof)
+    }
+    let (head, tail) = input.split_at(len);
+    *input = tail;
+    Ok(head)
+}
+
+/// [std::io::Read] adapter keeping track of the amount of bytes read through it.
+struct Counted<'a, R> where R: std::io::Read {
+    reader: &'a mut R,
+    position: &'a mut u64,
+}
+
+impl<'a, R> std::io::Read for Counted<'a, R> where R: std::io::Read {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        *self.position += size as u64;
+        Ok(size)
+    }
+}
+
+impl<'de> SliceDeserializer<'de> {
+    /// Create a new deserializer reading from the given in-memory `input`.
+    pub fn from_slice(input: &'de [u8]) -> Self {
+        Self {
+            borrow: Some(split_borrowed),
+            available: Some(|input| input.len()),
+            ..Self::new_with(Source::Owned(input))
+        }
+    }
+
+    /// The part of the input which hasn't been read yet.
+    pub fn remaining(&self) -> &'de [u8] {
+        match self.reader {
+            Source::Borrowed(ref input) => input,
+            Source::Owned(input) => input,
+        }
+    }
+
+    /// Remember the current position in the input, so that it can be returned to with [SliceDeserializer::rollback].
+    pub fn checkpoint(&self) -> Checkpoint<'de> {
+        Checkpoint {
+            input: self.remaining(),
+            position: self.position,
+        }
+    }
+
+    /// Return to a `checkpoint` taken from this deserializer, so that the input after it is read again.
+    ///
+    /// This allows reading ahead when the layout of a value depends on something stored after it, such as a [crate::TrailingOption] whose presence `bool` comes after the value: the value can be skipped over cheaply, for example by borrowing its strings, then read again in full only if it's present.
+    pub fn rollback(&mut self, checkpoint: Checkpoint<'de>) {
+        match &mut self.reader {
+            Source::Borrowed(input) => **input = checkpoint.input,
+            Source::Owned(input) => *input = checkpoint.input,
+        };
+        self.position = checkpoint.position;
+    }
+
+    /// Deserialize a value without consuming it, rolling back to where it started whether it could be deserialized or not.
+    pub fn peek<T>(&mut self) -> crate::Result<T> where T: serde::de::Deserialize<'de> {
+        let checkpoint = self.checkpoint();
+        let value = T::deserialize(&mut *self);
+        self.rollback(checkpoint);
+        value
+    }
+}
+
+/// A position in the input of a [SliceDeserializer], taken with [SliceDeserializer::checkpoint].
+#[derive(Clone, Copy, Debug)]
+pub struct Checkpoint<'de> {
+    /// The part of the input which hadn't been read yet.
+    input: &'de [u8],
+
+    /// The amount of bytes which had been read.
+    position: u64,
+}
+
+impl<'de, R> ReadDeserializer<'de, R> where R: std::io::Read + std::io::Seek {
+    /// Create a new deserializer reading from the given `reader`, starting at the absolute offset `base`.
+    ///
+    /// Positions, including the ones reported in errors and the ones given to [ReadDeserializer::seek_to], are relative to `base`, so that a file embedded in a larger archive can be read as if it were on its own.
+    pub fn with_base(reader: &'de mut R, base: u64) -> crate::Result<Self> {
+        reader.seek(std::io::SeekFrom::Start(base))?;
+        Ok(Self {
+            base,
+            ..Self::new(reader)
+        })
+    }
+
+    /// Move to the given `position`, relative to the base offset of the deserializer.
+    pub fn seek_to(&mut self, position: u64) -> crate::Result<()> {
+        let offset = self.base.checked_add(position).ok_or(crate::Error::Overflow)?;
+        self.reader.get_mut().seek(std::io::SeekFrom::Start(offset))?;
+        self.position = position;
+        Ok(())
+    }
 }
 
 impl<'de, R> ReadDeserializer<'de, R> where R: std::io::Read {
-    /// Read a ULEB128 value.
+    /// Create a new deserializer reading from the given `reader`.
+    pub fn new(reader: &'de mut R) -> Self {
+        Self::new_with(Source::Borrowed(reader))
+    }
+
+    /// Create a new deserializer taking ownership of the given `reader`, so that it doesn't have to outlive the deserialized values.
+    pub fn owned(reader: R) -> Self {
+        Self::new_with(Source::Owned(reader))
+    }
+
+    /// Create a new deserializer with the default options reading from the given `reader`.
+    fn new_with(reader: Source<'de, R>) -> Self {
+        Self {
+            reader,
+            treat_i16_len_as_unsigned: false,
+            version: None,
+            position: 0,
+            max_alloc: DEFAULT_MAX_ALLOC,
+            reject_inverted_ranges: false,
+            string_encoding: StringEncoding::Utf8,
+            scratch: vec![],
+            base: 0,
+            borrow: None,
+            available: None,
+        }
+    }
+
+    /// Interpret [i16] length prefixes with the sign bit set as [u16] counts, for formats that overload it.
+    ///
+    /// When disabled (the default), negative lengths are rejected with [crate::Error::Overflow].
+    pub fn treat_i16_len_as_unsigned(mut self, enabled: bool) -> Self {
+        self.treat_i16_len_as_unsigned = enabled;
+        self
+    }
+
+    /// Specify the version of the file being read, required by version-dependent values.
+    pub fn with_version(mut self, version: i32) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Limit the amount of bytes that can be allocated at once for a single value, to protect against corrupt or malicious length prefixes.
+    ///
+    /// Defaults to [DEFAULT_MAX_ALLOC].
+    pub fn max_alloc(mut self, bytes: usize) -> Self {
+        self.max_alloc = bytes;
+        self
+    }
+
+    /// Reject [crate::RangeI32]s whose `start` is greater than their `end` with [crate::Error::InvertedRange].
+    ///
+    /// Plain [std::ops::Range]s are never checked, since their bounds can't be compared without knowing their type.
+    pub fn reject_inverted_ranges(mut self, enabled: bool) -> Self {
+        self.reject_inverted_ranges = enabled;
+        self
+    }
+
+    /// Choose how the bytes of strings are turned into text, to read files containing strings which aren't valid UTF-8.
+    ///
+    /// Defaults to [StringEncoding::Utf8].
+    pub fn string_encoding(mut self, encoding: StringEncoding) -> Self {
+        self.string_encoding = encoding;
+        self
+    }
+
+    /// The version of the file being read, if it was specified.
+    pub fn version(&self) -> Option<i32> {
+        self.version
+    }
+
+    /// The amount of bytes read so far, which is the offset of the next value if reading started at the beginning of the file.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Check that the deserializer is at the given `expected` position, such as the offset at which the pointer table says the next section begins.
+    ///
+    /// Fails with [crate::Error::PositionMismatch] if it isn't.
+    pub fn expect_position(&self, expected: u64) -> crate::Result<()> {
+        match expected == self.position {
+            true => Ok(()),
+            false => Err(crate::Error::PositionMismatch { expected, actual: self.position }),
+        }
+    }
+
+    /// Read and discard `len` bytes, such as the ones of a section which isn't needed.
+    ///
+    /// Fails with [crate::Error::Eof] if the input ends before all of them are skipped.
+    pub fn skip(&mut self, len: usize) -> crate::Result<()> {
+        // In-memory slices can just be advanced.
+        if let Some(borrow) = self.borrow {
+            borrow(self.reader.get_mut(), len)?;
+            self.position += len as u64;
+            return Ok(())
+        }
+        let skipped = std::io::copy(&mut std::io::Read::take(self.reader.get_mut(), len as u64), &mut std::io::sink())?;
+        self.position += skipped;
+        match skipped == len as u64 {
+            true => Ok(()),
+            false => Err(crate::Error::Eof),
+        }
+    }
+
+    /// Read from the `reader` into `buf`, keeping track of the amount of bytes read.
+    fn read_into(&mut self, buf: &mut [u8]) -> crate::Result<usize> {
+        let size = self.reader.get_mut().read(buf)?;
+        self.position += size as u64;
+        Ok(size)
+    }
+
+    /// Fill `buf` from the `reader`, keeping track of the amount of bytes read.
+    ///
+    /// Fails with [crate::Error::Eof] if the input ends before `buf` is filled.
+    fn read_exact_into(&mut self, buf: &mut [u8]) -> crate::Result<()> {
+        self.reader.get_mut().read_exact(buf)?;
+        self.position += buf.len() as u64;
+        Ok(())
+    }
+
+    /// Read a primitive value with `read`, wrapping any error in [crate::Error::At] with the offset the value started at.
+    fn read_primitive<T>(&mut self, read: impl FnOnce(&mut Self) -> crate::Result<T>) -> crate::Result<T> {
+        let position = self.position;
+        read(self).map_err(|error| crate::Error::At { position, error: Box::new(error) })
+    }
+
+    /// Read a ULEB128 value as an [usize], failing with [crate::Error::Overflow] if it doesn't fit in one.
     pub fn read_uleb128(&mut self) -> crate::Result<usize> {
-        let size = leb128::read::unsigned(&mut self.reader).map_err(|_err| crate::Error::IO)?;
+        let size = self.read_uleb128_u64()?;
         let size = usize::try_from(size).map_err(|_err| crate::Error::Overflow)?;
         Ok(size)
     }
 
-    /// Read `N` bytes from the `reader`.
+    /// Read a ULEB128 value as an [u64], without narrowing it.
+    pub fn read_uleb128_u64(&mut self) -> crate::Result<u64> {
+        leb128::read::unsigned(&mut Counted { reader: &mut self.reader, position: &mut self.position }).map_err(|err| match err {
+            leb128::read::Error::IoError(err) => err.into(),
+            leb128::read::Error::Overflow => crate::Error::Overflow,
+        })
+    }
+
+    /// Read a signed LEB128 value.
+    pub fn read_sleb128(&mut self) -> crate::Result<i64> {
+        leb128::read::signed(&mut Counted { reader: &mut self.reader, position: &mut self.position }).map_err(|err| match err {
+            leb128::read::Error::IoError(err) => err.into(),
+            leb128::read::Error::Overflow => crate::Error::Overflow,
+        })
+    }
+
+    /// Read exactly `N` bytes from the `reader`.
     pub fn read_bytes<const N: usize>(&mut self) -> crate::Result<[u8; N]> {
         let mut buf = [0; N];
-        self.reader.read(&mut buf).map_err(|_err| crate::Error::IO)?;
+        self.read_exact_into(&mut buf)?;
+        Ok(buf)
+    }
+
+    /// Read up to `N` bytes from the `reader`, stopping early only at the end of the input.
+    ///
+    /// Returns the amount of bytes actually read along with the buffer, whose unread bytes are zeroed; useful to scan trailing data which may be truncated.
+    pub fn read_bytes_partial<const N: usize>(&mut self) -> crate::Result<(usize, [u8; N])> {
+        let mut buf = [0; N];
+        let mut size = 0;
+        while size < N {
+            match self.read_into(&mut buf[size..])? {
+                0 => break,
+                read => size += read,
+            }
+        }
+        Ok((size, buf))
+    }
+
+    /// Read an [i16] length prefix, according to the signedness configured for this deserializer.
+    pub fn read_i16_len(&mut self) -> crate::Result<usize> {
+        let buf = self.read_bytes::<2>()?;
+        match self.treat_i16_len_as_unsigned {
+            true => Ok(u16::from_le_bytes(buf) as usize),
+            false => usize::try_from(i16::from_le_bytes(buf)).map_err(|_err| crate::Error::Overflow),
+        }
+    }
+
+    /// Read an [i32] length prefix, rejecting negative values.
+    pub fn read_i32_len(&mut self) -> crate::Result<usize> {
+        let buf = self.read_bytes::<4>()?;
+        usize::try_from(i32::from_le_bytes(buf)).map_err(|_err| crate::Error::Overflow)
+    }
+
+    /// Read an [u32] length prefix, rejecting values which don't fit in an [usize].
+    pub fn read_u32_len(&mut self) -> crate::Result<usize> {
+        let buf = self.read_bytes::<4>()?;
+        usize::try_from(u32::from_le_bytes(buf)).map_err(|_err| crate::Error::Overflow)
+    }
+
+    /// Read an [i64] length prefix, rejecting negative values and values which don't fit in an [usize].
+    pub fn read_i64_len(&mut self) -> crate::Result<usize> {
+        let buf = self.read_bytes::<8>()?;
+        usize::try_from(i64::from_le_bytes(buf)).map_err(|_err| crate::Error::Overflow)
+    }
+
+    /// Read a SLEB128 length prefix, rejecting negative values and values which don't fit in an [usize].
+    pub fn read_sleb128_len(&mut self) -> crate::Result<usize> {
+        let len = self.read_sleb128()?;
+        usize::try_from(len).map_err(|_err| crate::Error::Overflow)
+    }
+
+    /// Read exactly `len` raw bytes, as written by `serialize_bytes`, and pass them to the `visitor`.
+    ///
+    /// Raw bytes are stored without a length prefix, so it has to be supplied by the caller; the bytes are borrowed if the input is an in-memory slice.
+    pub fn deserialize_bytes_exact<V>(&mut self, len: usize, visitor: V) -> crate::Result<V::Value> where V: serde::de::Visitor<'de> {
+        if let Some(borrow) = self.borrow {
+            let bytes = self.read_primitive(|de| {
+                let bytes = borrow(de.reader.get_mut(), len)?;
+                de.position += len as u64;
+                Ok(bytes)
+            })?;
+            return visitor.visit_borrowed_bytes(bytes)
+        }
+        let bytes = self.read_primitive(|de| de.read_vec(len))?;
+        visitor.visit_byte_buf(bytes)
+    }
+
+    /// Read a block made of its size in bytes, stored as `len_prefix`, followed by its contents, and deserialize a `T` out of exactly those contents.
+    ///
+    /// The `T` can't read past the end of the block, and fails with [crate::Error::TrailingBytes] if it doesn't use all of it; either way, the deserializer is left right after the block.
+    pub fn deserialize_sized_block<T>(&mut self, len_prefix: LenKind) -> crate::Result<T> where T: for<'a> crate::de::Deserialize<'a, T> {
+        let len = self.read_primitive(|de| match len_prefix {
+            LenKind::I16 => de.read_i16_len(),
+            LenKind::I32 => de.read_i32_len(),
+            LenKind::U32 => de.read_u32_len(),
+            LenKind::I64 => de.read_i64_len(),
+            LenKind::Uleb128 => de.read_uleb128(),
+        })?;
+        let start = self.position;
+        // In-memory slices don't need the block to be copied.
+        let block = match self.borrow {
+            Some(borrow) => self.read_primitive(|de| {
+                let bytes = borrow(de.reader.get_mut(), len)?;
+                de.position += len as u64;
+                Ok(std::borrow::Cow::Borrowed(bytes))
+            })?,
+            None => std::borrow::Cow::Owned(self.read_primitive(|de| de.read_vec(len))?),
+        };
+
+        let mut inner = SliceDeserializer {
+            treat_i16_len_as_unsigned: self.treat_i16_len_as_unsigned,
+            version: self.version,
+            position: start,
+            max_alloc: self.max_alloc,
+            reject_inverted_ranges: self.reject_inverted_ranges,
+            string_encoding: self.string_encoding,
+            ..SliceDeserializer::from_slice(&block)
+        };
+        let value = crate::de::Deserialize::deserialize(&mut inner)?;
+        let remaining = inner.remaining().len() as u64;
+        match remaining {
+            0 => Ok(value),
+            _ => Err(crate::Error::TrailingBytes { remaining: Some(remaining) }),
+        }
+    }
+
+    /// Read `size` bytes from the `reader` into a `Vec`.
+    ///
+    /// Fails without allocating if `size` is greater than the configured [ReadDeserializer::max_alloc].
+    pub fn read_vec(&mut self, size: usize) -> crate::Result<Vec<u8>> {
+        if size > self.max_alloc {
+            return Err(crate::Error::AllocationLimit { requested: size, limit: self.max_alloc })
+        }
+        let mut buf = vec![0; size];
+        self.read_exact_into(&mut buf)?;
         Ok(buf)
     }
 
     /// Read a ULEB128-sized `Vec` from the `reader`.
     pub fn read_uleb128_vec(&mut self) -> crate::Result<Vec<u8>> {
         let size = self.read_uleb128()?;
-        let mut buf = vec![0; size];
-        self.reader.read(&mut buf).map_err(|_err| crate::Error::IO)?;
-        Ok(buf)
+        self.read_vec(size)
+    }
+
+    /// Read a ULEB128-sized sequence of bytes from the `reader` into `buf`, replacing its contents but reusing its allocation.
+    ///
+    /// Fails without allocating if the size is greater than the configured [ReadDeserializer::max_alloc].
+    fn read_uleb128_into(&mut self, buf: &mut Vec<u8>) -> crate::Result<()> {
+        let size = self.read_uleb128()?;
+        self.read_len_into(size, buf)
+    }
+
+    /// Fail with [crate::Error::Truncated] if the input is known to have less than `len` bytes left, without reading any of them.
+    fn check_available(&mut self, len: usize) -> crate::Result<()> {
+        match self.available.map(|available| available(self.reader.get_mut())) {
+            Some(remaining) if remaining < len => Err(crate::Error::Truncated { requested: len, remaining }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that `len` elements of `element_size` bytes each can be allocated, without allocating them.
+    fn check_alloc(&self, len: usize, element_size: usize) -> crate::Result<()> {
+        let requested = len.saturating_mul(element_size);
+        match requested > self.max_alloc {
+            true => Err(crate::Error::AllocationLimit { requested, limit: self.max_alloc }),
+            false => Ok(()),
+        }
+    }
+
+    /// Read `size` bytes into `buf`, replacing its contents.
+    fn read_len_into(&mut self, size: usize, buf: &mut Vec<u8>) -> crate::Result<()> {
+        if size > self.max_alloc {
+            return Err(crate::Error::AllocationLimit { requested: size, limit: self.max_alloc })
+        }
+        buf.clear();
+        buf.resize(size, 0);
+        self.read_exact_into(buf)
+    }
+
+    /// Read the bytes of a string into the scratch buffer with `read_into`, so that its allocation can be reused, then visit it decoded with the configured [StringEncoding].
+    fn visit_scratch_string<V>(&mut self, read_into: impl FnOnce(&mut Self, &mut Vec<u8>) -> crate::Result<()>, visitor: V) -> crate::Result<V::Value> where V: serde::de::Visitor<'de> {
+        let position = self.position;
+        let mut buf = std::mem::take(&mut self.scratch);
+        let encoding = self.string_encoding;
+        let str = read_into(self, &mut buf)
+            .and_then(|()| encoding.decode(&buf))
+            .map_err(|error| crate::Error::At { position, error: Box::new(error) });
+        let value = match str {
+            Ok(std::borrow::Cow::Borrowed(str)) => visitor.visit_str(str),
+            Ok(std::borrow::Cow::Owned(string)) => visitor.visit_string(string),
+            Err(error) => Err(error),
+        };
+        self.scratch = buf;
+        value
+    }
+
+    /// Read `count` run-length encoded [MapTile]s from the `reader`.
+    pub fn deserialize_map_tiles(&mut self, count: usize) -> crate::Result<Vec<MapTile>> {
+        let mut tiles: Vec<MapTile> = vec![];
+        while tiles.len() < count {
+            let left = count - tiles.len();
+            // Each run is read as a whole, so that errors report the offset of its header.
+            let (tile, repeat) = self.read_primitive(|de| {
+                let [header] = de.read_bytes::<1>()?;
+                let tile_type = match header & crate::map::MAP_TILE_WIDE_TYPE {
+                    0 => u16::from(u8::from_le_bytes(de.read_bytes::<1>()?)),
+                    _ => u16::from_le_bytes(de.read_bytes::<2>()?),
+                };
+                let light = match header & crate::map::MAP_TILE_HAS_LIGHT {
+                    0 => u8::MAX,
+                    _ => u8::from_le_bytes(de.read_bytes::<1>()?),
+                };
+                let repeat = match header >> crate::map::MAP_TILE_REPEAT_SHIFT {
+                    0 => 0,
+                    1 => usize::from(u8::from_le_bytes(de.read_bytes::<1>()?)),
+                    2 => usize::from(u16::from_le_bytes(de.read_bytes::<2>()?)),
+                    _ => return Err(crate::Error::Overflow),
+                };
+                // A run must not go past the amount of tiles that was requested.
+                if repeat + 1 > left {
+                    return Err(crate::Error::Overflow)
+                }
+                Ok((MapTile { tile_type, light }, repeat))
+            })?;
+            tiles.extend(std::iter::repeat(tile).take(repeat + 1));
+        }
+        Ok(tiles)
+    }
+
+    /// Read a `width` by `height` grid of run-length encoded [MapTile]s stored column-major, and transpose it to row-major.
+    ///
+    /// The tile at column `x` and row `y` can then be found at index `y * width + x`.
+    pub fn decode_tiles_row_major(&mut self, width: usize, height: usize) -> crate::Result<Vec<MapTile>> {
+        let count = width.checked_mul(height).ok_or(crate::Error::Overflow)?;
+        let columns = self.deserialize_map_tiles(count)?;
+        let mut rows: Vec<MapTile> = Vec::with_capacity(count);
+        for y in 0..height {
+            for x in 0..width {
+                rows.push(columns[x * height + y]);
+            }
+        }
+        Ok(rows)
     }
 }
 
@@ -53,131 +650,217 @@ impl<'de, R> serde::de::Deserializer<'de> for &mut ReadDeserializer<'de, R> wher
 
     fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // With no info on what the next value is going to be, there's no way to determine it in Terraria world files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_any"))
     }
 
     fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `bool`s ("Bool") are stored as a single `u8` containing either `0` or `1`.
-        let buf = self.read_bytes::<1>()?;
-        match buf[0] {
-            0_u8 => visitor.visit_bool(false),
-            1_u8 => visitor.visit_bool(true),
+        let value = self.read_primitive(|de| match de.read_bytes::<1>()? {
+            [0_u8] => Ok(false),
+            [1_u8] => Ok(true),
             _ => Err(crate::Error::Overflow),
-        }
+        })?;
+        visitor.visit_bool(value)
     }
 
     fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `i8`s are stored in little-endian byte order.
-        let buf = self.read_bytes::<1>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<1>())?;
         visitor.visit_i8(i8::from_le_bytes(buf))
     }
 
     fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `i16`s ("Int16") are stored in little-endian byte order.
-        let buf = self.read_bytes::<2>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<2>())?;
         visitor.visit_i16(i16::from_le_bytes(buf))
     }
 
     fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `i32`s ("Int32") are stored in little-endian byte order.
-        let buf = self.read_bytes::<4>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<4>())?;
         visitor.visit_i32(i32::from_le_bytes(buf))
     }
 
     fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `i64`s are stored in little-endian byte order.
-        let buf = self.read_bytes::<8>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<8>())?;
         visitor.visit_i64(i64::from_le_bytes(buf))
     }
 
     fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `u8`s ("Byte") are stored in little-endian byte order.
-        let buf = self.read_bytes::<1>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<1>())?;
         visitor.visit_u8(u8::from_le_bytes(buf))
     }
 
     fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `u16`s are stored in little-endian byte order.
-        let buf = self.read_bytes::<2>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<2>())?;
         visitor.visit_u16(u16::from_le_bytes(buf))
     }
 
     fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `u32`s are stored in little-endian byte order.
-        let buf = self.read_bytes::<4>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<4>())?;
         visitor.visit_u32(u32::from_le_bytes(buf))
     }
 
     fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `u64`s are stored in little-endian byte order.
-        let buf = self.read_bytes::<8>()?;
+        let buf = self.read_primitive(|de| de.read_bytes::<8>())?;
         visitor.visit_u64(u64::from_le_bytes(buf))
     }
 
     fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `f32`s ("Single") are stored in little-endian byte order.
-        let mut buf: [u8; 4] = [0; 4];
-        self.reader.read(&mut buf).map_err(|_err| crate::Error::IO)?;
+        let buf = self.read_primitive(|de| de.read_bytes::<4>())?;
         visitor.visit_f32(f32::from_le_bytes(buf))
     }
 
     fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `f64`s ("Double") are stored in little-endian byte order.
-        let mut buf: [u8; 8] = [0; 8];
-        self.reader.read(&mut buf).map_err(|_err| crate::Error::IO)?;
+        let buf = self.read_primitive(|de| de.read_bytes::<8>())?;
         visitor.visit_f64(f64::from_le_bytes(buf))
     }
 
-    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // `char`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn deserialize_char<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        // `char`s are stored as strings containing only them.
+        let value = self.visit_scratch_string(|de, buf| de.read_uleb128_into(buf), crate::de::visitor::SingleCharVisitor)?;
+        visitor.visit_char(value)
     }
 
     fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // Ownership of the string must be taken in Terraria world files.
-        self.deserialize_string(visitor)
+        // Strings can only be borrowed if the whole input is already in memory and they don't need to be converted; otherwise, ownership of them must be taken.
+        let borrow = match self.borrow {
+            Some(borrow) if self.string_encoding == StringEncoding::Utf8 => borrow,
+            _ => return self.deserialize_string(visitor),
+        };
+        let str = self.read_primitive(|de| {
+            let len = de.read_uleb128()?;
+            let bytes = borrow(de.reader.get_mut(), len)?;
+            de.position += len as u64;
+            std::str::from_utf8(bytes).map_err(|err| crate::Error::utf8(bytes, err))
+        })?;
+        visitor.visit_borrowed_str(str)
     }
 
     fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // `str`s ("String") are stored as sequences of bytes.
-        let bytes = self.read_uleb128_vec()?;
-        let str = String::from_utf8(bytes).map_err(|_err| crate::Error::Overflow)?;
-        visitor.visit_string(str)
+        // `str`s ("String") are stored as sequences of bytes prefixed by their ULEB128 size.
+        self.visit_scratch_string(|de, buf| de.read_uleb128_into(buf), visitor)
     }
 
     fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // Terraria has no support for terminated byte-strings.
-        Err(crate::Error::Unsupported)
+        // Raw bytes have no length prefix, so they can only be read through `deserialize_bytes_exact`.
+        Err(crate::Error::Unsupported("deserialize_bytes"))
     }
 
     fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // Terraria has no support for terminated byte-strings.
-        Err(crate::Error::Unsupported)
+        // Raw bytes have no length prefix, so they can only be read through `deserialize_bytes_exact`.
+        Err(crate::Error::Unsupported("deserialize_byte_buf"))
     }
 
     fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // `None`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_option"))
     }
 
     fn deserialize_unit<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // Units `()` don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_unit"))
     }
 
     fn deserialize_unit_struct<V>(self, _name: &'static str, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // Named units can't be serialized in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_unit_struct"))
     }
 
-    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // `struct`s are handled by serializing their fields in order.
-        visitor.visit_newtype_struct(self)
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        match name {
+            // Version-dependent values receive the file version before their fields.
+            crate::de::VERSIONED => {
+                let version = self.version.ok_or(crate::Error::MissingVersion)?;
+                visitor.visit_seq(crate::de::accessor::Versioned { de: self, version: Some(version) })
+            },
+            // ULEB128 integers can't be told apart from the other integers by serde.
+            crate::de::ULEB128 => {
+                let value = self.read_uleb128_u64()?;
+                visitor.visit_u64(value)
+            },
+            crate::de::SLEB128 => {
+                let value = self.read_sleb128()?;
+                visitor.visit_i64(value)
+            },
+            // Trailing `u8`s are read only if the file hasn't ended yet.
+            crate::de::TRAILING_U8 => {
+                let mut buf: [u8; 1] = [0; 1];
+                match self.read_into(&mut buf)? {
+                    0 => visitor.visit_none(),
+                    _ => visitor.visit_u8(buf[0]),
+                }
+            },
+            // Nullable strings are decoded like the other strings, unless their size is `-1`.
+            crate::de::NULLABLE_STRING_I32 => {
+                let len = self.read_primitive(|de| match i32::from_le_bytes(de.read_bytes::<4>()?) {
+                    -1 => Ok(None),
+                    len => usize::try_from(len).map(Some).map_err(|_err| crate::Error::Overflow),
+                })?;
+                match len {
+                    Some(len) => self.visit_scratch_string(|de, buf| de.read_len_into(len, buf), visitor),
+                    None => visitor.visit_none(),
+                }
+            },
+            // Sized sequences know their size only after reading it.
+            crate::de::SEQ_I16 => {
+                let len = self.read_i16_len()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::SEQ_I32 => {
+                let len = self.read_i32_len()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::SEQ_U8 => {
+                let [len] = self.read_bytes::<1>()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len as usize, de: self })
+            },
+            crate::de::SEQ_U32 => {
+                let len = self.read_u32_len()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::SEQ_I64 => {
+                let len = self.read_i64_len()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::SEQ_ULEB128 => {
+                let len = self.read_uleb128()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::SEQ_SLEB128 => {
+                let len = self.read_sleb128_len()?;
+                visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+            },
+            crate::de::MAP_ULEB128 => {
+                let len = self.read_uleb128()?;
+                visitor.visit_map(crate::de::accessor::EntrySized { size: len, de: self })
+            },
+            // Sized sequences of bytes can be read all at once.
+            crate::de::BYTES_ULEB128 => {
+                let bytes = self.read_uleb128_vec()?;
+                visitor.visit_byte_buf(bytes)
+            },
+            crate::de::BYTES_U32 => {
+                let size = u32::from_le_bytes(self.read_bytes::<4>()?);
+                let size = usize::try_from(size).map_err(|_err| crate::Error::Overflow)?;
+                let bytes = self.read_vec(size)?;
+                visitor.visit_byte_buf(bytes)
+            },
+            // `struct`s are handled by serializing their fields in order.
+            _ => visitor.visit_newtype_struct(self),
+        }
     }
 
     fn deserialize_seq<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // Generic sequences should not be used in `serde-altar`; sized Vecs are available, though.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_seq"))
     }
 
     fn deserialize_tuple<V>(self, len: usize, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
@@ -185,14 +868,33 @@ impl<'de, R> serde::de::Deserializer<'de> for &mut ReadDeserializer<'de, R> wher
         visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
     }
 
-    fn deserialize_tuple_struct<V>(self, _name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // Tuple `struct`s are stored exactly in the same way as tuples.
-        self.deserialize_tuple(len, visitor)
+    fn deserialize_tuple_struct<V>(self, name: &'static str, len: usize, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        match name {
+            // Fixed amounts of bytes can be read all at once.
+            crate::de::BYTES => {
+                let bytes = self.read_primitive(|de| {
+                    de.check_available(len)?;
+                    de.read_vec(len)
+                })?;
+                visitor.visit_byte_buf(bytes)
+            },
+            // Ranges are stored as their `start` followed by their `end`, which may have to be checked.
+            crate::de::RANGE_I32 => {
+                let start: i32 = serde::de::Deserialize::deserialize(&mut *self)?;
+                let end: i32 = serde::de::Deserialize::deserialize(&mut *self)?;
+                if self.reject_inverted_ranges && start > end {
+                    return Err(crate::Error::InvertedRange { start, end })
+                }
+                visitor.visit_seq(serde::de::value::SeqDeserializer::new(vec![start, end].into_iter()))
+            },
+            // Tuple `struct`s are stored exactly in the same way as tuples.
+            _ => self.deserialize_tuple(len, visitor),
+        }
     }
 
     fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // Maps don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_map"))
     }
 
     fn deserialize_struct<V>(self, _name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
@@ -200,19 +902,22 @@ impl<'de, R> serde::de::Deserializer<'de> for &mut ReadDeserializer<'de, R> wher
         self.deserialize_tuple(fields.len(), visitor)
     }
 
-    fn deserialize_enum<V>(self, _name: &'static str, _variants: &'static [&'static str], _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
-        // `enum`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn deserialize_enum<V>(self, name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        // `enum`s are stored as the index of their variant as an `u8`, or an `i32` if they opted into it, followed by its fields.
+        match name {
+            crate::de::ENUM_I32 => Deserializer::deserialize_enum_i32(self, visitor),
+            _ => Deserializer::deserialize_enum_u8(self, visitor),
+        }
     }
 
     fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // Identifiers don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_identifier"))
     }
 
     fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
         // With no info on what the next value is going to be, there's no way to determine it in Terraria world files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("deserialize_ignored_any"))
     }
 
     fn is_human_readable(&self) -> bool {
@@ -223,22 +928,299 @@ impl<'de, R> serde::de::Deserializer<'de> for &mut ReadDeserializer<'de, R> wher
 
 impl<'de, R> crate::de::Deserializer<'de> for &mut ReadDeserializer<'de, R> where R: std::io::Read {
     fn deserialize_vec_i16flags<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
-        let len = i16::from_le_bytes(self.read_bytes::<2>()?) as usize;
-        visitor.visit_seq(crate::de::accessor::ValueSized { size: len / 8, de: self })
+        // The visitor reads the amount of bits itself, as it needs it to discard the padding of the last byte.
+        visitor.visit_vec_i16flags(crate::de::accessor::ValueSized { size: 2, de: self })
     }
 
     fn deserialize_vec_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
-        let len = i16::from_le_bytes(self.read_bytes::<2>()?) as usize;
-        visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+        let len = self.read_i16_len()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_i16(crate::de::accessor::ValueSized { size: len, de: self })
     }
 
     fn deserialize_vec_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
-        let len = i32::from_le_bytes(self.read_bytes::<4>()?) as usize;
-        visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+        let len = self.read_i32_len()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_i32(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_vec_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let [len] = self.read_bytes::<1>()?;
+        let len = len as usize;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_u8(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_vec_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let len = self.read_u32_len()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_u32(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_vec_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let len = self.read_i64_len()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_i64(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_enum_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        let [discriminant] = self.read_bytes::<1>()?;
+        visitor.visit_enum(crate::de::accessor::Discriminated { discriminant, de: self })
+    }
+
+    fn deserialize_enum_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        // Variant identifiers can only be deserialized from unsigned integers.
+        let discriminant = self.read_primitive(|de| u32::try_from(i32::from_le_bytes(de.read_bytes::<4>()?)).map_err(|_err| crate::Error::Overflow))?;
+        visitor.visit_enum(crate::de::accessor::Discriminated { discriminant, de: self })
     }
 
     fn deserialize_vec_uleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
         let len = self.read_uleb128()?;
-        visitor.visit_seq(crate::de::accessor::ValueSized { size: len, de: self })
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_uleb128(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_vec_sleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let len = self.read_sleb128_len()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_vec_sleb128(crate::de::accessor::ValueSized { size: len, de: self })
+    }
+
+    fn deserialize_rle<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let len = self.read_i32_len()?;
+        // Runs can expand to far more values than the bytes they take, so the expanded length has to be checked.
+        self.check_alloc(len, visitor.element_size())?;
+        // The amount of runs is only known once they are all read.
+        visitor.visit_rle(len, crate::de::accessor::ValueSized { size: usize::MAX, de: self })
+    }
+
+    fn deserialize_map_uleb128<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: Visitor<'de> {
+        let len = self.read_uleb128()?;
+        self.check_alloc(len, visitor.element_size())?;
+        visitor.visit_map_uleb128(crate::de::accessor::EntrySized { size: len, de: self })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SliceDeserializer;
+    use crate::VecI16;
+
+    /// A [VecI16] of `len` [i16]s whose length prefix has the sign bit set, as written by formats overloading it.
+    fn unsigned_vec_i16(len: u16) -> Vec<u8> {
+        let mut bytes = len.to_le_bytes().to_vec();
+        for value in 0..len {
+            bytes.extend_from_slice(&(value as i16).to_le_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn i16_len_as_unsigned() {
+        let bytes = unsigned_vec_i16(40000);
+        let mut de = SliceDeserializer::from_slice(&bytes).treat_i16_len_as_unsigned(true);
+        let vec = <VecI16<i16> as crate::Deserialize<i16>>::deserialize(&mut de).unwrap();
+        assert_eq!(vec.0.len(), 40000);
+        assert_eq!(vec.0[39999], 39999_u16 as i16);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn i16_len_as_unsigned_through_serde() {
+        let bytes = unsigned_vec_i16(40000);
+        let mut de = SliceDeserializer::from_slice(&bytes).treat_i16_len_as_unsigned(true);
+        let vec: VecI16<i16> = serde::Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!(vec.0.len(), 40000);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn i16_len_signed_rejects_negative() {
+        let bytes = unsigned_vec_i16(40000);
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        let result = <VecI16<i16> as crate::Deserialize<i16>>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::Overflow));
+    }
+
+    #[test]
+    fn read_bytes_partial_reports_short_read() {
+        let mut de = crate::ReadDeserializer::owned(std::io::Cursor::new(vec![1, 2, 3]));
+        let (size, buf) = de.read_bytes_partial::<8>().unwrap();
+        assert_eq!(size, 3);
+        assert_eq!(buf, [1, 2, 3, 0, 0, 0, 0, 0]);
+        assert_eq!(de.read_bytes_partial::<8>().unwrap(), (0, [0; 8]));
+        assert_eq!(de.read_bytes::<1>().err(), Some(crate::Error::Eof));
+    }
+
+    #[test]
+    fn read_bytes_partial_across_short_reads() {
+        // Chaining readers makes every read stop at the end of each of them.
+        let reader = std::io::Read::chain(&[1_u8, 2][..], &[3_u8, 4, 5][..]);
+        let mut de = crate::ReadDeserializer::owned(reader);
+        assert_eq!(de.read_bytes_partial::<4>().unwrap(), (4, [1, 2, 3, 4]));
+        assert_eq!(de.read_bytes_partial::<4>().unwrap(), (1, [5, 0, 0, 0]));
+    }
+
+    #[test]
+    fn unsupported_names_method() {
+        let result: crate::Result<std::collections::HashMap<u8, u8>> = crate::from_slice(&[0]);
+        assert_eq!(result.err(), Some(crate::Error::Unsupported("deserialize_map")));
+        let result: crate::Result<Option<u8>> = crate::from_slice(&[0]);
+        assert_eq!(result.err(), Some(crate::Error::Unsupported("deserialize_option")));
+    }
+
+    /// Read a [String] followed by its presence, allocating it only if it's present.
+    fn trailing_string(de: &mut SliceDeserializer) -> crate::Result<Option<String>> {
+        let checkpoint = de.checkpoint();
+        // Borrowing the string skips over it without copying it.
+        let _skipped: &str = serde::Deserialize::deserialize(&mut *de)?;
+        let present: bool = serde::Deserialize::deserialize(&mut *de)?;
+        if !present {
+            return Ok(None)
+        }
+        de.rollback(checkpoint);
+        let value = serde::Deserialize::deserialize(&mut *de)?;
+        let _present: bool = serde::Deserialize::deserialize(&mut *de)?;
+        Ok(Some(value))
+    }
+
+    #[test]
+    fn rollback_trailing_presence() {
+        let bytes = [3, b'a', b'b', b'c', 0, 2, b'd', b'e', 1];
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        assert_eq!(trailing_string(&mut de).unwrap(), None);
+        assert_eq!(de.position(), 5);
+        assert_eq!(trailing_string(&mut de).unwrap(), Some("de".to_string()));
+        assert_eq!(de.position(), 9);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn peek_does_not_consume() {
+        let bytes = [1, 0, 2];
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        assert_eq!(de.peek::<u16>().unwrap(), 1);
+        assert_eq!(de.position(), 0);
+        assert_eq!(de.peek::<u32>().err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Eof) }));
+        assert_eq!(de.remaining(), bytes);
+        let value: u16 = serde::Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!(value, 1);
+        assert_eq!(de.remaining(), [2]);
+    }
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum Liquid {
+        Empty,
+        Water(u8),
+        Lava(u8, bool),
+        Honey { amount: u8 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    #[serde(rename = "$serde_altar::EnumI32")]
+    enum Wide {
+        Small,
+        Large(i16),
+    }
+
+    impl_serialize_forward!(Liquid, Wide);
+
+    #[test]
+    fn enum_u8_roundtrip() {
+        let cases = [
+            (Liquid::Empty, vec![0]),
+            (Liquid::Water(255), vec![1, 255]),
+            (Liquid::Lava(3, true), vec![2, 3, 1]),
+            (Liquid::Honey { amount: 7 }, vec![3, 7]),
+        ];
+        for (liquid, bytes) in cases {
+            assert_eq!(crate::to_bytes(liquid.clone()).unwrap(), bytes);
+            assert_eq!(crate::from_slice::<Liquid>(&bytes).unwrap(), liquid);
+        }
+        assert!(crate::from_slice::<Liquid>(&[4]).is_err());
+    }
+
+    #[test]
+    fn enum_i32_roundtrip() {
+        assert_eq!(crate::to_bytes(Wide::Small).unwrap(), [0, 0, 0, 0]);
+        assert_eq!(crate::from_slice::<Wide>(&[0, 0, 0, 0]).unwrap(), Wide::Small);
+        let bytes = crate::to_bytes(Wide::Large(-2)).unwrap();
+        assert_eq!(bytes, [1, 0, 0, 0, 0xFE, 0xFF]);
+        assert_eq!(crate::from_slice::<Wide>(&bytes).unwrap(), Wide::Large(-2));
+    }
+
+    #[test]
+    fn enum_i32_rejects_negative() {
+        let bytes = (-1_i32).to_le_bytes();
+        assert_eq!(crate::from_slice::<Wide>(&bytes).err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Overflow) }));
+        assert!(crate::from_slice::<Wide>(&[2, 0, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn fixed_size_arrays() {
+        let guid: [u8; 16] = [0x00, 0x10, 0x20, 0x30, 0x40, 0x50, 0x60, 0x70, 0x80, 0x90, 0xA0, 0xB0, 0xC0, 0xD0, 0xE0, 0xF0];
+        let decoded: [u8; 16] = crate::from_slice(&guid).unwrap();
+        assert_eq!(decoded, guid);
+        let mut ser = crate::WriteSerializer::new(vec![]);
+        serde::Serialize::serialize(&decoded, &mut ser).unwrap();
+        assert_eq!(ser.finish().unwrap(), guid);
+        assert_eq!(
+            crate::from_slice::<[u8; 16]>(&guid[..15]).err(),
+            Some(crate::Error::At { position: 15, error: Box::new(crate::Error::Eof) }),
+        );
+
+        let bytes: Vec<u8> = [1_i32, -2, 3].iter().flat_map(|value| value.to_le_bytes()).collect();
+        assert_eq!(crate::from_slice::<[i32; 3]>(&bytes).unwrap(), [1, -2, 3]);
+    }
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Embedded {
+        version: i32,
+        revision: u16,
+    }
+
+    #[test]
+    fn read_with_base_offset() {
+        // Seven bytes of another file, followed by the embedded one.
+        let mut archive = vec![0xAA; 7];
+        archive.extend_from_slice(&279_i32.to_le_bytes());
+        archive.extend_from_slice(&5_u16.to_le_bytes());
+        archive.push(1);
+        let mut reader = std::io::Cursor::new(archive);
+
+        let mut de = crate::ReadDeserializer::with_base(&mut reader, 7).unwrap();
+        let embedded: Embedded = serde::Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!(embedded, Embedded { version: 279, revision: 5 });
+        assert_eq!(de.position(), 6);
+
+        de.seek_to(4).unwrap();
+        let revision: u16 = serde::Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!(revision, 5);
+
+        // Error positions are relative to the base offset as well.
+        let error = <u32 as serde::Deserialize>::deserialize(&mut de).unwrap_err();
+        assert_eq!(error, crate::Error::At { position: 6, error: Box::new(crate::Error::Eof) });
+    }
+
+    #[test]
+    fn chars() {
+        let mut ser = crate::WriteSerializer::new(vec![]);
+        serde::Serialize::serialize(&'\u{00E9}', &mut ser).unwrap();
+        let bytes = ser.finish().unwrap();
+        assert_eq!(bytes, [2, 0xC3, 0xA9]);
+        assert_eq!(crate::from_slice::<char>(&bytes), Ok('\u{00E9}'));
+
+        let mut latin1 = crate::SliceDeserializer::from_slice(&[1, 0xE9]).string_encoding(crate::StringEncoding::Latin1);
+        assert_eq!(<char as serde::Deserialize>::deserialize(&mut latin1), Ok('\u{00E9}'));
+
+        assert_eq!(
+            crate::from_slice::<char>(&[0]).err(),
+            Some(crate::Error::Message("invalid length 0, expected a string of a single char".to_string())),
+        );
+        assert_eq!(
+            crate::from_slice::<char>(&[3, b'a', 0xC3, 0xA9]).err(),
+            Some(crate::Error::Message("invalid length 2, expected a string of a single char".to_string())),
+        );
+        assert_eq!(crate::from_slice::<char>(&[2, b'a']).err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Eof) }));
     }
 }
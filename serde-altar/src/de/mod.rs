@@ -2,17 +2,262 @@ mod deserialize;
 mod deserializer;
 mod visitor;
 mod accessor;
+mod validator;
+mod probe;
+mod progress;
 
 pub use deserialize::Deserialize;
 pub use deserializer::Deserializer;
 pub use visitor::Visitor;
 
 pub use deserializer::ReadDeserializer;
+pub use deserializer::SliceDeserializer;
+pub use deserializer::Checkpoint;
+pub use deserializer::DEFAULT_MAX_ALLOC;
+pub use deserializer::StringEncoding;
+pub use deserializer::LenKind;
+pub use validator::SectionValidator;
+
+/// Name of the newtype `struct`s whose sequence starts with the version of the file being deserialized.
+pub(crate) const VERSIONED: &str = "$serde_altar::Versioned";
+
+/// Name of the newtype `struct`s wrapping a ULEB128 integer.
+pub(crate) const ULEB128: &str = "$serde_altar::ULEB128";
+
+/// Name of the newtype `struct`s wrapping a signed LEB128 integer.
+pub(crate) const SLEB128: &str = "$serde_altar::SLEB128";
+
+/// Name of the newtype `struct`s wrapping a [u8] which may be missing at the end of the file.
+pub(crate) const TRAILING_U8: &str = "$serde_altar::TrailingU8";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an [i16].
+pub(crate) const SEQ_I16: &str = "$serde_altar::SeqI16";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an [i32].
+pub(crate) const SEQ_I32: &str = "$serde_altar::SeqI32";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an [u8].
+pub(crate) const SEQ_U8: &str = "$serde_altar::SeqU8";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an [u32].
+pub(crate) const SEQ_U32: &str = "$serde_altar::SeqU32";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an [i64].
+pub(crate) const SEQ_I64: &str = "$serde_altar::SeqI64";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an ULEB128.
+pub(crate) const SEQ_ULEB128: &str = "$serde_altar::SeqULEB128";
+
+/// Name of the newtype `struct`s wrapping a sequence prefixed by its size as an SLEB128.
+pub(crate) const SEQ_SLEB128: &str = "$serde_altar::SeqSLEB128";
+
+/// Name of the newtype `struct`s wrapping a table of keys and values prefixed by its size as an ULEB128.
+pub(crate) const MAP_ULEB128: &str = "$serde_altar::MapULEB128";
+
+/// Name of the newtype `struct`s wrapping a sequence of bytes prefixed by its size as an ULEB128.
+pub(crate) const BYTES_ULEB128: &str = "$serde_altar::BytesULEB128";
+
+/// Name of the newtype `struct`s wrapping a sequence of bytes prefixed by its size as an [u32].
+pub(crate) const BYTES_U32: &str = "$serde_altar::BytesU32";
+
+/// Name of the newtype `struct`s wrapping a string prefixed by its size as an [i32], which is `-1` if the string is null.
+pub(crate) const NULLABLE_STRING_I32: &str = "$serde_altar::NullableStringI32";
+
+/// Name of the `enum`s whose variant is selected by a leading [i32] discriminant instead of an [u8] one.
+///
+/// `enum`s opt into it with `#[serde(rename = "$serde_altar::EnumI32")]`.
+pub(crate) const ENUM_I32: &str = "$serde_altar::EnumI32";
+
+/// Name of the tuple `struct`s wrapping a fixed amount of bytes, which can be read all at once.
+pub(crate) const BYTES: &str = "$serde_altar::Bytes";
+
+/// Name of the tuple `struct`s wrapping a [crate::RangeI32], which may have to be checked.
+pub(crate) const RANGE_I32: &str = "$serde_altar::RangeI32";
 
 
 /// Deserialize any [Deserialize]able struct using a [Read]er as a source.
 pub fn from_reader<'de, R, T>(reader: &'de mut R) -> crate::Result<T> where T: Deserialize<'de, T>, R: std::io::Read {
-    let mut de = ReadDeserializer { reader };
+    let mut de = ReadDeserializer::new(reader);
+    let t = Deserialize::deserialize(&mut de)?;
+    Ok(t)
+}
+
+/// Deserialize any [Deserialize]able struct using a [Read]er as a source, checking that the value uses all of it.
+///
+/// Fails with [crate::Error::TrailingBytes] if the `reader` has anything left after the value; at most one more byte is read to find out, so the amount left isn't known.
+pub fn from_reader_exact<'de, R, T>(reader: &'de mut R) -> crate::Result<T> where T: Deserialize<'de, T>, R: std::io::Read {
+    let mut de = ReadDeserializer::new(reader);
+    let t = Deserialize::deserialize(&mut de)?;
+    let mut buf = [0; 1];
+    loop {
+        match de.reader.get_mut().read(&mut buf) {
+            Ok(0) => return Ok(t),
+            Ok(_) => return Err(crate::Error::TrailingBytes { remaining: None }),
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Deserialize any [Deserialize]able struct using a [Read]er as a source, taking ownership of it.
+///
+/// Unlike [from_reader], the deserialized value can't borrow anything from the `reader`.
+pub fn from_reader_owned<R, T>(reader: R) -> crate::Result<T> where T: for<'de> Deserialize<'de, T>, R: std::io::Read {
+    let mut de = ReadDeserializer::owned(reader);
     let t = Deserialize::deserialize(&mut de)?;
     Ok(t)
 }
+
+/// Deserialize any [Deserialize]able struct from a raw deflate stream, such as the ones written by .NET's `DeflateStream`, read from `reader`.
+///
+/// Only the bytes of the compressed stream are consumed, so `reader` is left right after its end.
+#[cfg(feature = "flate")]
+pub fn from_reader_deflate<R, T>(reader: R) -> crate::Result<T> where T: for<'de> Deserialize<'de, T>, R: std::io::BufRead {
+    from_reader_owned(flate2::bufread::DeflateDecoder::new(reader))
+}
+
+/// Deserialize any [Deserialize]able struct from a zlib stream, made of a raw deflate stream between a header and a checksum, read from `reader`.
+///
+/// Only the bytes of the compressed stream are consumed, so `reader` is left right after its end.
+#[cfg(feature = "flate")]
+pub fn from_reader_zlib<R, T>(reader: R) -> crate::Result<T> where T: for<'de> Deserialize<'de, T>, R: std::io::BufRead {
+    from_reader_owned(flate2::bufread::ZlibDecoder::new(reader))
+}
+
+/// Deserialize any [Deserialize]able struct using a [Read]er as a source, reporting the amount of bytes read so far to `callback`.
+///
+/// `total` is the expected size of the input: `callback` is called roughly every hundredth of it, and once more when the value is complete.
+pub fn from_reader_with_progress<R, T, F>(reader: &mut R, total: u64, callback: F) -> crate::Result<T> where T: for<'de> Deserialize<'de, T>, R: std::io::Read, F: FnMut(u64) {
+    let mut progress = progress::ProgressReader {
+        reader,
+        callback,
+        position: 0,
+        reported: 0,
+        interval: (total / progress::PROGRESS_STEPS).max(1),
+    };
+    let t = from_reader(&mut progress)?;
+    (progress.callback)(progress.position);
+    Ok(t)
+}
+
+/// Deserialize any [Deserialize]able struct from the start of a slice of bytes, ignoring anything left after it.
+///
+/// This is the lenient counterpart of [from_slice], useful to read a value at the start of a larger buffer; use [from_slice] to check that the value uses all of the `bytes`.
+pub fn from_bytes<T>(bytes: &[u8]) -> crate::Result<T> where T: for<'de> Deserialize<'de, T> {
+    let mut de = SliceDeserializer::from_slice(bytes);
+    Deserialize::deserialize(&mut de)
+}
+
+/// Deserialize any [Deserialize]able struct from a slice of bytes, borrowing strings from it where possible.
+///
+/// Fails with [crate::Error::TrailingBytes] if the value doesn't use all of the `bytes`.
+pub fn from_slice<'de, T>(bytes: &'de [u8]) -> crate::Result<T> where T: Deserialize<'de, T> {
+    let mut de = SliceDeserializer::from_slice(bytes);
+    let t = Deserialize::deserialize(&mut de)?;
+    let remaining = de.remaining().len() as u64;
+    match remaining {
+        0 => Ok(t),
+        _ => Err(crate::Error::TrailingBytes { remaining: Some(remaining) }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A reader which is interrupted before each successful read.
+    struct InterruptedReader<R> {
+        reader: R,
+        interrupted: bool,
+    }
+
+    impl<R> std::io::Read for InterruptedReader<R> where R: std::io::Read {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.interrupted = !self.interrupted;
+            match self.interrupted {
+                true => Err(std::io::Error::from(std::io::ErrorKind::Interrupted)),
+                false => self.reader.read(buf),
+            }
+        }
+    }
+
+    #[test]
+    fn from_reader_exact_all_used() {
+        let mut reader = std::io::Cursor::new(279_i32.to_le_bytes());
+        assert_eq!(from_reader_exact::<_, i32>(&mut reader), Ok(279));
+    }
+
+    #[test]
+    fn from_reader_exact_trailing_data() {
+        let mut reader = std::io::Cursor::new([1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(from_reader_exact::<_, u16>(&mut reader), Err(crate::Error::TrailingBytes { remaining: None }));
+        // The rest of the input is left unread, besides the byte telling it is there.
+        assert_eq!(reader.position(), 3);
+    }
+
+    #[test]
+    fn from_reader_exact_interrupted() {
+        let mut reader = InterruptedReader { reader: &[1_u8, 0, 9][..], interrupted: false };
+        assert_eq!(from_reader_exact::<_, u16>(&mut reader), Err(crate::Error::TrailingBytes { remaining: None }));
+        let mut reader = InterruptedReader { reader: &[1_u8, 0][..], interrupted: false };
+        assert_eq!(from_reader_exact::<_, u16>(&mut reader), Ok(1));
+    }
+
+    #[test]
+    fn from_bytes_ignores_trailing_bytes() {
+        let bytes = [1, 0, 9, 9];
+        assert_eq!(from_bytes::<u16>(&bytes), Ok(1));
+        assert_eq!(from_slice::<u16>(&bytes), Err(crate::Error::TrailingBytes { remaining: Some(2) }));
+        assert_eq!(from_slice::<u16>(&bytes[..2]), Ok(1));
+        assert!(from_bytes::<u32>(&bytes[..3]).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "flate"))]
+mod flate_tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Section {
+        name: String,
+        values: [u16; 32],
+    }
+
+    impl_serialize_forward!(Section);
+
+    fn section() -> Section {
+        Section { name: "Shimmer".repeat(4), values: [269; 32] }
+    }
+
+    #[test]
+    fn deflate_roundtrip() {
+        let mut bytes = crate::to_writer_deflate(vec![], section()).unwrap();
+        assert!(bytes.len() < crate::to_bytes(section()).unwrap().len());
+        bytes.extend_from_slice(b"next");
+
+        let mut reader = std::io::Cursor::new(bytes);
+        assert_eq!(from_reader_deflate::<_, Section>(&mut reader).unwrap(), section());
+        // The bytes after the compressed stream are left for the next value.
+        let rest = &reader.get_ref()[reader.position() as usize..];
+        assert_eq!(rest, b"next");
+    }
+
+    #[test]
+    fn zlib_roundtrip() {
+        let mut bytes = crate::to_writer_zlib(vec![], section()).unwrap();
+        // The header of a zlib stream compressed with the default level.
+        assert_eq!(bytes[..2], [0x78, 0x9C]);
+        bytes.extend_from_slice(b"next");
+
+        let mut reader = std::io::Cursor::new(bytes);
+        assert_eq!(from_reader_zlib::<_, Section>(&mut reader).unwrap(), section());
+        let rest = &reader.get_ref()[reader.position() as usize..];
+        assert_eq!(rest, b"next");
+    }
+
+    #[test]
+    fn deflate_truncated() {
+        let bytes = crate::to_writer_deflate(vec![], section()).unwrap();
+        assert!(from_reader_deflate::<_, Section>(&bytes[..bytes.len() / 2]).is_err());
+    }
+}
@@ -0,0 +1,29 @@
+/// Deserializer reading nothing, which succeeds only for types deserialized from a single [u8].
+///
+/// Used to pick the bulk byte path for sequences of bytes, since their element type can't be known otherwise.
+pub struct ByteProbe;
+
+impl ByteProbe {
+    /// Whether `T` is deserialized from a single [u8].
+    pub fn accepts<'de, T>() -> bool where T: serde::de::Deserialize<'de> {
+        T::deserialize(ByteProbe).is_ok()
+    }
+}
+
+impl<'de> serde::de::Deserializer<'de> for ByteProbe {
+    type Error = crate::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        Err(crate::Error::Unsupported("deserialize_any"))
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error> where V: serde::de::Visitor<'de> {
+        visitor.visit_u8(0)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
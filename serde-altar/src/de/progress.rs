@@ -0,0 +1,44 @@
+/// The amount of progress reports made over the whole input, not counting the final one.
+pub(crate) const PROGRESS_STEPS: u64 = 100;
+
+/// [std::io::Read] adapter reporting the amount of bytes read through it every `interval` bytes.
+pub(crate) struct ProgressReader<'a, R, F> where R: std::io::Read, F: FnMut(u64) {
+    pub(crate) reader: &'a mut R,
+    pub(crate) callback: F,
+
+    /// The amount of bytes read so far.
+    pub(crate) position: u64,
+
+    /// The amount of bytes read when the callback was last called.
+    pub(crate) reported: u64,
+
+    /// The minimum amount of bytes to read between two calls of the callback.
+    pub(crate) interval: u64,
+}
+
+impl<'a, R, F> std::io::Read for ProgressReader<'a, R, F> where R: std::io::Read, F: FnMut(u64) {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let size = self.reader.read(buf)?;
+        self.position += size as u64;
+        if self.position - self.reported >= self.interval {
+            self.reported = self.position;
+            (self.callback)(self.position);
+        }
+        Ok(size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn progress_reaches_total() {
+        let bytes = crate::to_bytes(crate::VecI32(vec![0_i32; 1000])).unwrap();
+        let total = bytes.len() as u64;
+        let mut reports: Vec<u64> = vec![];
+        let value: crate::VecI32<i32> = crate::from_reader_with_progress(&mut &bytes[..], total, |position| reports.push(position)).unwrap();
+        assert_eq!(value.0.len(), 1000);
+        assert!(reports.len() > 2);
+        assert!(reports.windows(2).all(|pair| pair[0] <= pair[1]));
+        assert_eq!(reports.last(), Some(&total));
+    }
+}
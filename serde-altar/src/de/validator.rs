@@ -0,0 +1,73 @@
+use crate::de::Deserialize;
+use crate::de::ReadDeserializer;
+
+/// Wrapper around a [ReadDeserializer] checking that each section of a file ends where the pointer table says the next one begins.
+pub struct SectionValidator<'a, 'de, R> where R: std::io::Read {
+    de: &'a mut ReadDeserializer<'de, R>,
+
+    /// The offsets at which each section begins, as stored in the pointer table.
+    pointers: Vec<u64>,
+
+    /// The index of the next section to deserialize.
+    section: usize,
+}
+
+impl<'a, 'de, R> SectionValidator<'a, 'de, R> where R: std::io::Read {
+    /// Wrap the given deserializer, which should have started reading at the beginning of the file, validating it against the given section `pointers`.
+    pub fn new(de: &'a mut ReadDeserializer<'de, R>, pointers: Vec<u64>) -> Self {
+        Self { de, pointers, section: 0 }
+    }
+
+    /// The index of the next section to deserialize.
+    pub fn section(&self) -> usize {
+        self.section
+    }
+
+    /// Deserialize the next section, checking that it begins and ends at the offsets stored in the pointer table.
+    pub fn deserialize_section<T>(&mut self) -> crate::Result<T> where T: Deserialize<'de, T> {
+        self.check_position(self.section)?;
+        let value = Deserialize::deserialize(&mut *self.de)?;
+        self.section += 1;
+        self.check_position(self.section)?;
+        Ok(value)
+    }
+
+    /// Check that the deserializer is at the beginning of the given `section`, if the pointer table contains it.
+    fn check_position(&self, section: usize) -> crate::Result<()> {
+        match self.pointers.get(section) {
+            Some(&expected) if expected != self.de.position() => Err(crate::Error::SectionDesync {
+                section,
+                expected,
+                actual: self.de.position(),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SliceDeserializer;
+
+    #[test]
+    fn sections_in_sync() {
+        let bytes = [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0];
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        let mut validator = SectionValidator::new(&mut de, vec![0, 4, 12]);
+        let first: i32 = validator.deserialize_section().unwrap();
+        let second: (i32, i32) = validator.deserialize_section().unwrap();
+        assert_eq!((first, second), (1, (2, 3)));
+        assert_eq!(validator.section(), 2);
+    }
+
+    #[test]
+    fn section_too_short() {
+        let bytes = [1, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0];
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        let mut validator = SectionValidator::new(&mut de, vec![0, 4, 12]);
+        let _first: i32 = validator.deserialize_section().unwrap();
+        let result: crate::Result<i32> = validator.deserialize_section();
+        assert_eq!(result.err(), Some(crate::Error::SectionDesync { section: 2, expected: 12, actual: 8 }));
+    }
+}
@@ -1,18 +1,136 @@
 use std::fmt::Formatter;
 use serde::de::SeqAccess;
 use crate::VecI16Flags;
+use crate::VecI32F32;
 use crate::VecULEB128;
-use crate::VecI16;
-use crate::VecI32;
+use crate::VecSLEB128;
+use crate::RleVec;
+use crate::RleCount;
+use crate::MapULEB128;
+use crate::BossFlags;
+use crate::Leb128U64;
+use crate::VersionedU8;
+use crate::BoolGrid2D;
+use crate::BlobList;
+use crate::ShopInventory;
+use crate::Tlv;
+use crate::RangeI32;
+use crate::VersionedArray;
+use crate::BitmaskU64;
+use crate::TileShape;
+use crate::OptionalColor;
+use crate::TrailingOption;
+use crate::OptionBool;
+use crate::CharMap;
+use crate::Difficulty;
+use crate::DeltaPosList;
+use crate::CreativePower;
+use crate::CreativePowers;
+use crate::FixedPoint;
+use crate::DeltaF32Series;
+use crate::PackedNBit;
+use crate::TileCounts;
+use crate::BoolArray;
+use crate::Backgrounds;
+use crate::NullableStringI32;
+use crate::MapHeader;
+use crate::NamedKillCounts;
+use crate::PaintColor;
+use crate::Guid;
+use crate::EventTimers;
+use crate::MapTileColor;
+use crate::ShimmerData;
+use crate::de::deserializer::MAX_PREALLOC;
+
 
 /// Visitor for [VecI16Flags], containing `bool`s.
 pub struct VecI16FlagsVisitor;
 /// Visitor for [VecULEB128], containing `T`s.
 pub struct VecULEB128Visitor<T> (pub std::marker::PhantomData<T>);
-/// Visitor for [VecI16], containing `T`s.
-pub struct VecI16Visitor<T> (pub std::marker::PhantomData<T>);
-/// Visitor for [VecULEB128], containing `T`s.
-pub struct VecI32Visitor<T> (pub std::marker::PhantomData<T>);
+/// Visitor for [VecSLEB128], containing `T`s.
+pub struct VecSLEB128Visitor<T> (pub std::marker::PhantomData<T>);
+/// Visitor for [RleVec], containing runs of `T`s repeated `C` times.
+pub struct RleVecVisitor<T, C> (pub std::marker::PhantomData<(T, C)>);
+/// Visitor for [MapULEB128], containing `K` keys and `V` values.
+pub struct MapULEB128Visitor<K, V> (pub std::marker::PhantomData<(K, V)>);
+/// Visitor for [VecI32F32], reading all the values at once.
+pub struct VecI32F32Visitor;
+/// Visitor for [BossFlags], preceded by the file version.
+pub struct BossFlagsVisitor;
+/// Visitor for [Leb128U64].
+pub struct Leb128U64Visitor;
+/// Visitor for [VersionedU8], which may be missing.
+pub struct VersionedU8Visitor;
+/// Visitor for [BoolGrid2D].
+pub struct BoolGrid2DVisitor;
+/// Seed and visitor for `count` bits packed 8 per byte, least significant first.
+pub struct PackedBitsVisitor { pub count: usize }
+/// Visitor for [BlobList].
+pub struct BlobListVisitor;
+/// Seed and visitor for a single blob of a [BlobList].
+pub struct BlobVisitor;
+/// Visitor for [ShopInventory], containing `N` item ids.
+pub struct ShopInventoryVisitor<const N: usize>;
+/// Visitor for [Tlv].
+pub struct TlvVisitor;
+/// Seed and visitor for the value of a [Tlv].
+pub struct TlvValueVisitor;
+/// Visitor for [RangeI32].
+pub struct RangeI32Visitor;
+/// Visitor for [VersionedArray], preceded by the file version.
+pub struct VersionedArrayVisitor<const N: usize, const COUNTED_SINCE: i32>;
+/// Visitor for [BitmaskU64], keeping `N` bits.
+pub struct BitmaskU64Visitor<const N: usize>;
+/// Visitor for [TileShape].
+pub struct TileShapeVisitor;
+/// Visitor for [OptionalColor].
+pub struct OptionalColorVisitor;
+/// Visitor for [OptionBool], containing a `T`.
+pub struct OptionBoolVisitor<T> (pub std::marker::PhantomData<T>);
+/// Visitor for [TrailingOption], containing a `T`.
+pub struct TrailingOptionVisitor<T> (pub std::marker::PhantomData<T>);
+/// Visitor for [CreativePowers].
+pub struct CreativePowersVisitor;
+/// Visitor for [FixedPoint], scaled by `SCALE`.
+pub struct FixedPointVisitor<const SCALE: i64>;
+/// Visitor for [DeltaF32Series], with deltas scaled by `SCALE`.
+pub struct DeltaF32SeriesVisitor<const SCALE: i64>;
+/// Visitor for [PackedNBit], containing `BITS`-wide values.
+pub struct PackedNBitVisitor<const BITS: u8>;
+/// Visitor for [TileCounts], containing `N` counts.
+pub struct TileCountsVisitor<const N: usize>;
+/// Visitor for [Backgrounds].
+pub struct BackgroundsVisitor;
+/// Visitor for [NullableStringI32].
+pub struct NullableStringI32Visitor;
+/// Seed and visitor for `len` bytes, read all at once.
+pub struct BytesVisitor { pub len: usize }
+/// Visitor for [MapHeader], checking its magic bytes.
+pub struct MapHeaderVisitor;
+/// Visitor for [CharMap].
+pub struct CharMapVisitor;
+/// Visitor for [Difficulty], preceded by the file version.
+pub struct DifficultyVisitor;
+/// Visitor for [DeltaPosList].
+pub struct DeltaPosListVisitor;
+/// Seed and visitor for a signed LEB128 integer.
+pub struct Sleb128Visitor;
+/// Visitor for [NamedKillCounts].
+pub struct NamedKillCountsVisitor;
+/// Visitor for [BoolArray], containing `N` `bool`s.
+pub struct BoolArrayVisitor<const N: usize>;
+/// Visitor for [PaintColor], checking its id.
+pub struct PaintColorVisitor;
+/// Visitor for [Guid], reading all the bytes at once.
+pub struct GuidVisitor;
+/// Visitor for [EventTimers], preceded by the file version.
+pub struct EventTimersVisitor;
+/// Visitor for [MapTileColor], checking its header.
+pub struct MapTileColorVisitor;
+/// Visitor for [ShimmerData], preceded by the file version.
+pub struct ShimmerDataVisitor;
+/// Visitor for `char`s, stored as strings containing only them.
+pub struct SingleCharVisitor;
 
 
 /// Custom visitor trait with support for the weird Terraria array serialization.
@@ -33,6 +151,30 @@ pub trait Visitor<'de> : serde::de::Visitor<'de> {
         Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
     }
 
+    /// The input contains a [VecSLEB128].
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_vec_sleb128<S: serde::de::SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let _ = seq;
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
+    }
+
+    /// The input contains a [crate::MapULEB128].
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_map_uleb128<M: serde::de::MapAccess<'de>>(self, map: M) -> Result<Self::Value, M::Error> {
+        let _ = map;
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Map, &self))
+    }
+
+    /// The input contains a [RleVec] expanding to `len` values.
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_rle<S: serde::de::SeqAccess<'de>>(self, len: usize, seq: S) -> Result<Self::Value, S::Error> {
+        let _ = (len, seq);
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
+    }
+
     /// The input contains a [VecI16].
     ///
     /// The default implementation fails with a type error.
@@ -48,86 +190,934 @@ pub trait Visitor<'de> : serde::de::Visitor<'de> {
         let _ = seq;
         Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
     }
+
+    /// The input contains a [crate::VecU8].
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_vec_u8<S: serde::de::SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let _ = seq;
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
+    }
+
+    /// The input contains a [crate::VecU32].
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_vec_u32<S: serde::de::SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let _ = seq;
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
+    }
+
+    /// The input contains a [crate::VecI64].
+    ///
+    /// The default implementation fails with a type error.
+    fn visit_vec_i64<S: serde::de::SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        let _ = seq;
+        Err(serde::de::Error::invalid_type(serde::de::Unexpected::Seq, &self))
+    }
+
+    /// The amount of bytes each element of a sized sequence, or each entry of a sized table, takes in memory once visited, so that its length can be checked against [crate::ReadDeserializer::max_alloc] before reading it.
+    ///
+    /// The default implementation returns `0`, which disables the check.
+    fn element_size(&self) -> usize {
+        0
+    }
 }
 
 impl<'de> serde::de::Visitor<'de> for VecI16FlagsVisitor {
     type Value = VecI16Flags;
 
     fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-        formatter.write_str("a u16-sized list of bools")
+        formatter.write_str("an i16-sized list of packed bools")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i16 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let count = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let bits = seq.next_element_seed(PackedBitsVisitor { count })?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(VecI16Flags(bits))
     }
 }
 
 impl<'de> Visitor<'de> for VecI16FlagsVisitor {
-    fn visit_vec_i16flags<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
-        let mut inner_vec: Vec<bool> = vec![];
-        while let Some(element) = seq.next_element::<u8>()? {
-            let mut bits = vec![
-                (element & 0b0000_0001) != 0,
-                (element & 0b0000_0010) != 0,
-                (element & 0b0000_0100) != 0,
-                (element & 0b0000_1000) != 0,
-                (element & 0b0001_0000) != 0,
-                (element & 0b0010_0000) != 0,
-                (element & 0b0100_0000) != 0,
-                (element & 0b1000_0000) != 0,
-            ];
-            inner_vec.append(&mut bits);
-        }
-        Ok(VecI16Flags(inner_vec))
+    fn visit_vec_i16flags<S: SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        serde::de::Visitor::visit_seq(self, seq)
     }
 }
 
-impl<'de, T> serde::de::Visitor<'de> for VecI16Visitor<T> {
-    type Value = VecI16<T>;
+impl<'de, T> serde::de::Visitor<'de> for VecULEB128Visitor<T> where T: serde::de::Deserialize<'de> {
+    type Value = VecULEB128<T>;
 
     fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-        formatter.write_str("a u16-sized list")
+        formatter.write_str("a uleb128-sized list")
     }
-}
 
-impl<'de, T> Visitor<'de> for VecI16Visitor<T> where T: crate::de::Deserialize<'de, T> {
-    fn visit_vec_i16<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let inner_vec = v.iter()
+            .map(|byte| T::deserialize(serde::de::IntoDeserializer::<E>::into_deserializer(*byte)))
+            .collect::<Result<Vec<T>, E>>()?;
+        Ok(VecULEB128(inner_vec))
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
         let mut inner_vec: Vec<T> = vec![];
         while let Some(element) = seq.next_element()? {
             inner_vec.push(element);
         }
-        Ok(VecI16(inner_vec))
+        Ok(VecULEB128(inner_vec))
     }
 }
 
-impl<'de, T> serde::de::Visitor<'de> for VecI32Visitor<T> {
-    type Value = VecI32<T>;
+impl<'de, T> Visitor<'de> for VecULEB128Visitor<T> where T: crate::de::Deserialize<'de, T> {
+    fn visit_vec_uleb128<S: SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        serde::de::Visitor::visit_seq(self, seq)
+    }
 
-    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-        formatter.write_str("a u32-sized list")
+    fn element_size(&self) -> usize {
+        std::mem::size_of::<T>()
     }
 }
 
-impl<'de, T> Visitor<'de> for VecI32Visitor<T> where T: crate::de::Deserialize<'de, T> {
-    fn visit_vec_i32<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+impl<'de, T> serde::de::Visitor<'de> for VecSLEB128Visitor<T> where T: serde::de::Deserialize<'de> {
+    type Value = VecSLEB128<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sleb128-sized list")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
         let mut inner_vec: Vec<T> = vec![];
         while let Some(element) = seq.next_element()? {
             inner_vec.push(element);
         }
-        Ok(VecI32(inner_vec))
+        Ok(VecSLEB128(inner_vec))
     }
 }
 
-impl<'de, T> serde::de::Visitor<'de> for VecULEB128Visitor<T> {
-    type Value = VecULEB128<T>;
+impl<'de, T> Visitor<'de> for VecSLEB128Visitor<T> where T: crate::de::Deserialize<'de, T> {
+    fn visit_vec_sleb128<S: SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+        serde::de::Visitor::visit_seq(self, seq)
+    }
+
+    fn element_size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BossFlagsVisitor {
+    type Value = BossFlags;
 
     fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
-        formatter.write_str("a uleb128-sized list")
+        formatter.write_str("a version-dependent list of bools")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let count = BossFlags::count_for_version(version);
+        let mut inner_vec: Vec<bool> = Vec::with_capacity(count);
+        for index in 0..count {
+            let element = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index, &self))?;
+            inner_vec.push(element);
+        }
+        Ok(BossFlags(inner_vec))
     }
 }
 
-impl<'de, T> Visitor<'de> for VecULEB128Visitor<T> where T: crate::de::Deserialize<'de, T> {
-    fn visit_vec_uleb128<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
-        let mut inner_vec: Vec<T> = vec![];
-        while let Some(element) = seq.next_element()? {
+impl<'de> serde::de::Visitor<'de> for Leb128U64Visitor {
+    type Value = Leb128U64;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an uleb128 integer")
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Leb128U64(v))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for VersionedU8Visitor {
+    type Value = VersionedU8;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an optional trailing u8")
+    }
+
+    fn visit_u8<E: serde::de::Error>(self, v: u8) -> Result<Self::Value, E> {
+        Ok(VersionedU8(v))
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(VersionedU8(0))
+    }
+}
+
+impl<'de, K, V> serde::de::Visitor<'de> for MapULEB128Visitor<K, V> where K: serde::de::Deserialize<'de>, V: serde::de::Deserialize<'de> {
+    type Value = MapULEB128<K, V>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a uleb128-sized table")
+    }
+
+    fn visit_map<M: serde::de::MapAccess<'de>>(self, mut map: M) -> Result<Self::Value, M::Error> {
+        let mut entries: Vec<(K, V)> = vec![];
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(MapULEB128(entries))
+    }
+}
+
+impl<'de, K, V> Visitor<'de> for MapULEB128Visitor<K, V> where K: crate::de::Deserialize<'de, K>, V: crate::de::Deserialize<'de, V> {
+    fn visit_map_uleb128<M: serde::de::MapAccess<'de>>(self, map: M) -> Result<Self::Value, M::Error> {
+        serde::de::Visitor::visit_map(self, map)
+    }
+
+    fn element_size(&self) -> usize {
+        std::mem::size_of::<(K, V)>()
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for VecI32F32Visitor {
+    type Value = VecI32F32;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized list of f32s")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).ok()
+            .and_then(|len| len.checked_mul(4))
+            .ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let bytes = seq.next_element_seed(BytesVisitor { len })?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        // Values are always stored little-endian, whatever the endianness of the host.
+        let values = bytes.chunks_exact(4).map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect();
+        Ok(VecI32F32(values))
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for PackedBitsVisitor {
+    type Value = Vec<bool>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: serde::de::Deserializer<'de> {
+        let len = (self.count / 8) + if (self.count % 8) != 0 { 1 } else { 0 };
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, len, self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for PackedBitsVisitor {
+    type Value = Vec<bool>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sequence of packed bits")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut inner_vec: Vec<bool> = Vec::with_capacity(self.count.min(MAX_PREALLOC));
+        while inner_vec.len() < self.count {
+            let element: u8 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(inner_vec.len() / 8, &self))?;
+            // Padding bits of the last byte are discarded.
+            let bits = (self.count - inner_vec.len()).min(8);
+            inner_vec.extend((0..bits).map(|index| (element & (1 << index)) != 0));
+        }
+        Ok(inner_vec)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() * 8 < self.count {
+            return Err(serde::de::Error::invalid_length(v.len(), &self))
+        }
+        // Padding bits of the last byte are discarded.
+        Ok((0..self.count).map(|index| (v[index / 8] & (1 << (index % 8))) != 0).collect())
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BoolGrid2DVisitor {
+    type Value = BoolGrid2D;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a two-dimensional grid of bools")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let width: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let height: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        let count = usize::try_from(width).ok()
+            .zip(usize::try_from(height).ok())
+            .and_then(|(width, height)| width.checked_mul(height))
+            .ok_or_else(|| serde::de::Error::custom("BoolGrid2D dimensions are invalid"))?;
+        let bits = seq.next_element_seed(PackedBitsVisitor { count })?.ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
+        Ok(BoolGrid2D { width, height, bits })
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for BlobVisitor {
+    type Value = Vec<u8>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::BYTES_ULEB128, self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BlobVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an uleb128-sized blob of bytes")
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BlobListVisitor {
+    type Value = BlobList;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized list of blobs")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut inner_vec: Vec<Vec<u8>> = vec![];
+        while let Some(element) = seq.next_element_seed(BlobVisitor)? {
             inner_vec.push(element);
         }
-        Ok(VecULEB128(inner_vec))
+        Ok(BlobList(inner_vec))
+    }
+}
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for ShopInventoryVisitor<N> {
+    type Value = ShopInventory<N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{} i32 item ids", N)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != N * 4 {
+            return Err(serde::de::Error::invalid_length(v.len(), &self))
+        }
+        let mut slots = [0; N];
+        for (slot, chunk) in slots.iter_mut().zip(v.chunks_exact(4)) {
+            *slot = i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+        Ok(ShopInventory(slots))
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for TlvValueVisitor {
+    type Value = Vec<u8>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::BYTES_U32, self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for TlvValueVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an u32-sized value")
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for TlvVisitor {
+    type Value = Tlv;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a tag-length-value record")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let tag: u16 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let value = seq.next_element_seed(TlvValueVisitor)?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(Tlv { tag, value })
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for RangeI32Visitor {
+    type Value = RangeI32;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32 range")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let start = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let end = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(RangeI32(start..end))
+    }
+}
+
+impl<'de, const N: usize, const COUNTED_SINCE: i32> serde::de::Visitor<'de> for VersionedArrayVisitor<N, COUNTED_SINCE> {
+    type Value = VersionedArray<N, COUNTED_SINCE>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a version-dependent list of i32s")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let counted = version >= COUNTED_SINCE;
+        let count = match counted {
+            true => {
+                let count: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+                usize::try_from(count).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(count.into()), &self))?
+            },
+            false => N,
+        };
+        let mut values: Vec<i32> = vec![];
+        for index in 0..count {
+            let element = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index, &self))?;
+            values.push(element);
+        }
+        Ok(VersionedArray { counted, values })
+    }
+}
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for BitmaskU64Visitor<N> {
+    type Value = BitmaskU64<N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "a u64 bitmask of {} bits", N)
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        let _unused_bits = BitmaskU64::<N>::UNUSED_BITS;
+        // Bits past the first `N` are discarded.
+        let mut bits = [false; N];
+        for (index, bit) in bits.iter_mut().enumerate() {
+            *bit = (v >> index) & 1 != 0;
+        }
+        Ok(BitmaskU64 { bits })
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for TileShapeVisitor {
+    type Value = TileShape;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a tile shape between 0 and 5")
+    }
+
+    fn visit_u8<E: serde::de::Error>(self, v: u8) -> Result<Self::Value, E> {
+        TileShape::from_value(v).ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v.into()), &self))
+    }
+}
+
+impl<'de, T> serde::de::Visitor<'de> for OptionBoolVisitor<T> where T: serde::de::Deserialize<'de> {
+    type Value = OptionBool<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a presence flag optionally followed by a value")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let present: bool = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        match present {
+            true => {
+                let value = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+                Ok(OptionBool(Some(value)))
+            },
+            false => Ok(OptionBool(None)),
+        }
+    }
+}
+
+impl<'de, T> serde::de::Visitor<'de> for TrailingOptionVisitor<T> where T: serde::de::Deserialize<'de> {
+    type Value = TrailingOption<T>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a value followed by its presence")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        // The value has to be read even if it turns out to be missing.
+        let value = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let present = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        Ok(TrailingOption { value, present })
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for OptionalColorVisitor {
+    type Value = OptionalColor;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a presence flag optionally followed by a color")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let present: bool = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        match present {
+            true => {
+                let color = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+                Ok(OptionalColor(Some(color)))
+            },
+            false => Ok(OptionalColor(None)),
+        }
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for CreativePowersVisitor {
+    type Value = CreativePowers;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a terminated list of creative powers")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let mut inner_vec: Vec<CreativePower> = vec![];
+        loop {
+            let id: i16 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(inner_vec.len(), &self))?;
+            if id == crate::CREATIVE_POWERS_END {
+                break;
+            }
+            // The type of the value depends on the power.
+            let power = match CreativePower::is_slider(id) {
+                true => CreativePower::Slider { id, value: seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(inner_vec.len(), &self))? },
+                false => CreativePower::Toggle { id, enabled: seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(inner_vec.len(), &self))? },
+            };
+            inner_vec.push(power);
+        }
+        Ok(CreativePowers(inner_vec))
+    }
+}
+
+impl<'de, const SCALE: i64> serde::de::Visitor<'de> for FixedPointVisitor<SCALE> {
+    type Value = FixedPoint<SCALE>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a scaled i32")
+    }
+
+    fn visit_i32<E: serde::de::Error>(self, v: i32) -> Result<Self::Value, E> {
+        Ok(FixedPoint(v as f64 / SCALE as f64))
+    }
+}
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for TileCountsVisitor<N> {
+    type Value = TileCounts<N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{} i32 tile counts", N)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != N * 4 {
+            return Err(serde::de::Error::invalid_length(v.len(), &self))
+        }
+        let counts = v.chunks_exact(4).map(|chunk| i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])).collect();
+        Ok(TileCounts(counts))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BackgroundsVisitor {
+    type Value = Backgrounds;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{} i32 background styles", crate::world::BACKGROUNDS_LEN)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != crate::world::BACKGROUNDS_LEN * 4 {
+            return Err(serde::de::Error::invalid_length(v.len(), &self))
+        }
+        let mut values = v.chunks_exact(4).map(|chunk| i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]));
+        let mut next = || values.next().unwrap_or_default();
+        Ok(Backgrounds {
+            tree_x: [next(), next(), next()],
+            tree_styles: [next(), next(), next(), next()],
+            cave_x: [next(), next(), next()],
+            cave_styles: [next(), next(), next(), next()],
+        })
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_tuple_struct(crate::de::BYTES, self.len, self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{} bytes", self.len)
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        self.visit_byte_buf(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        if v.len() != self.len {
+            return Err(serde::de::Error::invalid_length(v.len(), &self))
+        }
+        Ok(v)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for NullableStringI32Visitor {
+    type Value = NullableStringI32;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized string, or -1 for null")
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(NullableStringI32(None))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(NullableStringI32(Some(v.to_string())))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, v: String) -> Result<Self::Value, E> {
+        Ok(NullableStringI32(Some(v)))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for MapHeaderVisitor {
+    type Value = MapHeader;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a map file header")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let magic: [u8; 7] = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        if magic != crate::RELOGIC_MAGIC {
+            return Err(serde::de::Error::invalid_value(serde::de::Unexpected::Bytes(&magic), &self))
+        }
+        let file_type = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
+        let revision = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(3, &self))?;
+        let favorites = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(4, &self))?;
+        Ok(MapHeader { version, file_type, revision, favorites })
+    }
+}
+
+impl<'de, const SCALE: i64> serde::de::Visitor<'de> for DeltaF32SeriesVisitor<SCALE> {
+    type Value = DeltaF32Series<SCALE>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized delta-compressed series of f32s")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let mut values: Vec<f32> = vec![];
+        if len > 0 {
+            let first: f32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+            values.push(first);
+            let mut previous = first as f64;
+            for index in 1..len {
+                let delta: i16 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index + 1, &self))?;
+                previous += delta as f64 / SCALE as f64;
+                values.push(previous as f32);
+            }
+        }
+        Ok(DeltaF32Series(values))
+    }
+}
+
+impl<'de, const BITS: u8> serde::de::Visitor<'de> for PackedNBitVisitor<BITS> {
+    type Value = PackedNBit<BITS>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "an i32-sized list of {}-bit values", BITS)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        if !matches!(BITS, 1 | 2 | 4 | 8) {
+            return Err(serde::de::Error::custom("PackedNBit width is not a power of two no greater than 8"))
+        }
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let per_byte = usize::from(8 / BITS);
+        let mask = ((1_u16 << BITS) - 1) as u8;
+        let mut values: Vec<u8> = vec![];
+        while values.len() < len {
+            let byte: u8 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(values.len() / per_byte + 1, &self))?;
+            // Padding values of the last byte are discarded.
+            let count = (len - values.len()).min(per_byte);
+            values.extend((0..count).map(|index| (byte >> (index * usize::from(BITS))) & mask));
+        }
+        Ok(PackedNBit(values))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for CharMapVisitor {
+    type Value = CharMap;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized table of chars and strings")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let mut map = std::collections::BTreeMap::new();
+        for index in 0..len {
+            let key: u32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 1, &self))?;
+            let key = char::from_u32(key).ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(key.into()), &self))?;
+            let value: String = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 2, &self))?;
+            map.insert(key, value);
+        }
+        Ok(CharMap(map))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for DifficultyVisitor {
+    type Value = Difficulty;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a version-dependent world difficulty")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        match version >= crate::world::GAME_MODE_VERSION {
+            true => Ok(Difficulty::GameMode(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?)),
+            false => Ok(Difficulty::Expert(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?)),
+        }
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for Sleb128Visitor {
+    type Value = i64;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error> where D: serde::de::Deserializer<'de> {
+        deserializer.deserialize_newtype_struct(crate::de::SLEB128, self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for Sleb128Visitor {
+    type Value = i64;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a sleb128 integer")
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+}
+
+impl<T, C> RleVecVisitor<T, C> {
+    /// Read runs of values from `seq` until `len` values have been read, expanding each of them.
+    fn expand_runs<'de, S: SeqAccess<'de>>(self, len: usize, mut seq: S) -> Result<RleVec<T, C>, S::Error> where Self: serde::de::Visitor<'de>, T: serde::de::Deserialize<'de> + Clone, C: RleCount {
+        let mut values: Vec<T> = Vec::with_capacity(len.min(MAX_PREALLOC));
+        let mut index = 1;
+        while values.len() < len {
+            let value: T = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index, &self))?;
+            let count: C = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index + 1, &self))?;
+            let repeat: usize = count.into();
+            // A run must not go past the amount of values that was stored.
+            if repeat >= len - values.len() {
+                return Err(serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(repeat as u64), &self))
+            }
+            values.extend(std::iter::repeat(value).take(repeat + 1));
+            index += 2;
+        }
+        Ok(RleVec(values, std::marker::PhantomData))
+    }
+}
+
+impl<'de, T, C> serde::de::Visitor<'de> for RleVecVisitor<T, C> where T: serde::de::Deserialize<'de> + Clone, C: RleCount {
+    type Value = RleVec<T, C>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized run-length encoded list")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        self.expand_runs(len, seq)
+    }
+}
+
+impl<'de, T, C> Visitor<'de> for RleVecVisitor<T, C> where T: crate::de::Deserialize<'de, T> + Clone, C: RleCount {
+    fn visit_rle<S: SeqAccess<'de>>(self, len: usize, seq: S) -> Result<Self::Value, S::Error> {
+        self.expand_runs(len, seq)
+    }
+
+    fn element_size(&self) -> usize {
+        std::mem::size_of::<T>()
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for DeltaPosListVisitor {
+    type Value = DeltaPosList;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized delta-coded list of positions")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let mut positions: Vec<(i32, i32)> = vec![];
+        if len > 0 {
+            let x: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+            let y: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(2, &self))?;
+            positions.push((x, y));
+            for index in 1..len {
+                let (previous_x, previous_y) = positions[index - 1];
+                let delta_x = seq.next_element_seed(Sleb128Visitor)?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 1, &self))?;
+                let delta_y = seq.next_element_seed(Sleb128Visitor)?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 2, &self))?;
+                let x = i64::from(previous_x).checked_add(delta_x).and_then(|x| i32::try_from(x).ok()).ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(delta_x), &self))?;
+                let y = i64::from(previous_y).checked_add(delta_y).and_then(|y| i32::try_from(y).ok()).ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(delta_y), &self))?;
+                positions.push((x, y));
+            }
+        }
+        Ok(DeltaPosList(positions))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for NamedKillCountsVisitor {
+    type Value = NamedKillCounts;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an i32-sized list of names and kill counts")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let len: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let len = usize::try_from(len).map_err(|_err| serde::de::Error::invalid_value(serde::de::Unexpected::Signed(len.into()), &self))?;
+        let mut entries = vec![];
+        for index in 0..len {
+            let name: String = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 1, &self))?;
+            let count: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index * 2 + 2, &self))?;
+            entries.push((name, count));
+        }
+        Ok(NamedKillCounts(entries))
+    }
+}
+
+impl<'de, const N: usize> serde::de::Visitor<'de> for BoolArrayVisitor<N> {
+    type Value = BoolArray<N>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "{} bools", N)
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        // Each byte is read as a regular `bool`, so it is subject to the same checks.
+        let mut values = [false; N];
+        for (index, value) in values.iter_mut().enumerate() {
+            *value = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(index, &self))?;
+        }
+        Ok(BoolArray(values))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for PaintColorVisitor {
+    type Value = PaintColor;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(formatter, "a paint color between 0 and {}", PaintColor::MAX)
+    }
+
+    fn visit_u8<E: serde::de::Error>(self, v: u8) -> Result<Self::Value, E> {
+        PaintColor::new(v).ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v.into()), &self))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for GuidVisitor {
+    type Value = Guid;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("16 guid bytes")
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        let bytes = <[u8; 16]>::try_from(v).map_err(|_err| serde::de::Error::invalid_length(v.len(), &self))?;
+        Ok(Guid(bytes))
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for EventTimersVisitor {
+    type Value = EventTimers;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("version-dependent event timers")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        let sandstorm = match version >= crate::world::SANDSTORM_VERSION {
+            true => Some(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?),
+            false => None,
+        };
+        let lantern_night = match version >= crate::world::LANTERN_NIGHT_VERSION {
+            true => Some(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(2, &self))?),
+            false => None,
+        };
+        Ok(EventTimers { sandstorm, lantern_night })
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for MapTileColorVisitor {
+    type Value = MapTileColor;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a single map tile with a narrow type")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let header: u8 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        // Wide types and repeated tiles can't be represented.
+        if header & !crate::map::MAP_TILE_HAS_LIGHT != 0 {
+            return Err(serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(header.into()), &self))
+        }
+        let color = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?;
+        let light = match header & crate::map::MAP_TILE_HAS_LIGHT {
+            0 => u8::MAX,
+            _ => seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(2, &self))?,
+        };
+        Ok(MapTileColor { color, light })
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for ShimmerDataVisitor {
+    type Value = ShimmerData;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("version-dependent shimmer data")
+    }
+
+    fn visit_seq<S: SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+        let version: i32 = seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(0, &self))?;
+        // Older files end before the shimmer fields, so nothing must be read from them.
+        match version >= crate::world::SHIMMER_VERSION {
+            true => Ok(ShimmerData(Some(seq.next_element()?.ok_or_else(|| serde::de::Error::invalid_length(1, &self))?))),
+            false => Ok(ShimmerData(None)),
+        }
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for SingleCharVisitor {
+    type Value = char;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a string of a single char")
+    }
+
+    fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let mut chars = v.chars();
+        match (chars.next(), chars.next()) {
+            (Some(value), None) => Ok(value),
+            _ => Err(serde::de::Error::invalid_length(v.chars().count(), &self)),
+        }
     }
 }
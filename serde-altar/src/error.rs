@@ -1,23 +1,180 @@
 /// Base error of this library.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 pub enum Error {
 
     /// Error raised by a consumer of this library.
     Message(String),
 
-    /// Tried to serialize a type that is not supported by the "altar" file type.
-    Unsupported,
+    /// Tried to (de)serialize a type that is not supported by the "altar" file type, through the named method.
+    Unsupported(&'static str),
 
     /// An IO error occurred while (de)serializing a value.
-    IO,
+    ///
+    /// The underlying error is shared, so that [Error] can still be cloned.
+    IO(std::sync::Arc<std::io::Error>),
 
     /// An overflow of some kind occurred while (de)serializing a value.
     Overflow,
 
+    /// A string was not valid UTF-8.
+    Utf8 {
+        /// The amount of bytes of the string which were valid.
+        valid_up_to: usize,
+        /// The invalid byte sequence, or the remaining bytes if the string ended in the middle of a character.
+        sequence: Vec<u8>,
+    },
+
+    /// The input ended in the middle of a value.
+    Eof,
+
+    /// A value needed more bytes than the input had left, which was known before reading any of them.
+    Truncated {
+        /// The amount of bytes the value needed.
+        requested: usize,
+        /// The amount of bytes left in the input.
+        remaining: usize,
+    },
+
+    /// Tried to deserialize a version-dependent value without specifying the version of the file.
+    MissingVersion,
+
+    /// A length prefix asked for more memory than the deserializer is allowed to allocate.
+    AllocationLimit {
+        /// The amount of bytes that would have been allocated.
+        requested: usize,
+        /// The maximum amount of bytes that can be allocated.
+        limit: usize,
+    },
+
+    /// A section of the file did not begin at the offset stored in the pointer table.
+    SectionDesync {
+        /// The index of the section in the pointer table.
+        section: usize,
+        /// The offset stored in the pointer table.
+        expected: u64,
+        /// The offset the deserializer was at.
+        actual: u64,
+    },
+
+    /// The deserializer was not at the offset it was expected to be at.
+    PositionMismatch {
+        /// The offset the deserializer should have been at.
+        expected: u64,
+        /// The offset the deserializer was at.
+        actual: u64,
+    },
+
+    /// A range ended before it started.
+    InvertedRange {
+        /// The start of the range.
+        start: i32,
+        /// The end of the range.
+        end: i32,
+    },
+
+    /// The checksum trailing the file did not match the one computed while reading it.
+    ChecksumMismatch {
+        /// The checksum stored in the file.
+        expected: u32,
+        /// The checksum computed while reading.
+        actual: u32,
+    },
+
+    /// A value repeated in the footer of the file did not match the one in its header.
+    FooterMismatch {
+        /// The name of the mismatched value.
+        field: &'static str,
+        /// The value stored in the header.
+        header: u64,
+        /// The value stored in the footer.
+        footer: u64,
+    },
+
+    /// A value was deserialized without using all of the input.
+    TrailingBytes {
+        /// The amount of bytes left after the value, if it is known; readers aren't read any further than needed to find out there is something left.
+        remaining: Option<u64>,
+    },
+
+    /// A primitive value could not be read; wraps the error with the offset the value started at.
+    At {
+        /// The amount of bytes read before the value.
+        position: u64,
+        /// The error raised while reading the value.
+        error: Box<Error>,
+    },
+
 }
 
 /// `serde-altar` errors are regular `std::error::Error`.
-impl std::error::Error for Error {}
+impl std::error::Error for Error {
+
+    /// IO errors are caused by the underlying [std::io::Error], and positioned errors by the error they wrap.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::IO(err) => Some(err.as_ref()),
+            Error::At { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+
+}
+
+/// `serde-altar` errors can be compared, to check which error occurred.
+impl PartialEq for Error {
+
+    /// IO errors are compared by their [std::io::ErrorKind], since [std::io::Error] can't be compared.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Error::Message(a), Error::Message(b)) => a == b,
+            (Error::Unsupported(a), Error::Unsupported(b)) => a == b,
+            (Error::IO(a), Error::IO(b)) => a.kind() == b.kind(),
+            (Error::Overflow, Error::Overflow) => true,
+            (Error::Utf8 { valid_up_to: a_valid_up_to, sequence: a_sequence }, Error::Utf8 { valid_up_to: b_valid_up_to, sequence: b_sequence }) => a_valid_up_to == b_valid_up_to && a_sequence == b_sequence,
+            (Error::Eof, Error::Eof) => true,
+            (Error::Truncated { requested: a_requested, remaining: a_remaining }, Error::Truncated { requested: b_requested, remaining: b_remaining }) => a_requested == b_requested && a_remaining == b_remaining,
+            (Error::MissingVersion, Error::MissingVersion) => true,
+            (Error::AllocationLimit { requested: a_requested, limit: a_limit }, Error::AllocationLimit { requested: b_requested, limit: b_limit }) => a_requested == b_requested && a_limit == b_limit,
+            (Error::SectionDesync { section: a_section, expected: a_expected, actual: a_actual }, Error::SectionDesync { section: b_section, expected: b_expected, actual: b_actual }) => a_section == b_section && a_expected == b_expected && a_actual == b_actual,
+            (Error::PositionMismatch { expected: a_expected, actual: a_actual }, Error::PositionMismatch { expected: b_expected, actual: b_actual }) => a_expected == b_expected && a_actual == b_actual,
+            (Error::InvertedRange { start: a_start, end: a_end }, Error::InvertedRange { start: b_start, end: b_end }) => a_start == b_start && a_end == b_end,
+            (Error::ChecksumMismatch { expected: a_expected, actual: a_actual }, Error::ChecksumMismatch { expected: b_expected, actual: b_actual }) => a_expected == b_expected && a_actual == b_actual,
+            (Error::FooterMismatch { field: a_field, header: a_header, footer: a_footer }, Error::FooterMismatch { field: b_field, header: b_header, footer: b_footer }) => a_field == b_field && a_header == b_header && a_footer == b_footer,
+            (Error::TrailingBytes { remaining: a_remaining }, Error::TrailingBytes { remaining: b_remaining }) => a_remaining == b_remaining,
+            (Error::At { position: a_position, error: a_error }, Error::At { position: b_position, error: b_error }) => a_position == b_position && a_error == b_error,
+            _ => false,
+        }
+    }
+
+}
+
+/// IO errors can be converted into `serde-altar` errors, keeping the original error.
+impl From<std::io::Error> for Error {
+
+    /// Wrap the IO error in [Error::IO], unless it was caused by the input ending early, which is an [Error::Eof].
+    fn from(err: std::io::Error) -> Self {
+        match err.kind() {
+            std::io::ErrorKind::UnexpectedEof => Error::Eof,
+            _ => Error::IO(err.into()),
+        }
+    }
+
+}
+
+/// Helpers to build `serde-altar` errors from the errors of other libraries.
+impl Error {
+
+    /// Describe why the given `bytes` are not valid UTF-8, as reported by `err`.
+    pub(crate) fn utf8(bytes: &[u8], err: std::str::Utf8Error) -> Self {
+        let valid_up_to = err.valid_up_to();
+        let end = match err.error_len() {
+            Some(len) => valid_up_to + len,
+            None => bytes.len(),
+        };
+        Error::Utf8 { valid_up_to, sequence: bytes[valid_up_to..end].to_vec() }
+    }
+
+}
 
 /// `serde-altar` errors also are `serde::ser::Error`.
 impl serde::ser::Error for Error {
@@ -47,9 +204,22 @@ impl std::fmt::Display for Error {
         match self {
             // Custom errors should display their own message.
             Error::Message(msg) => f.write_str(msg),
-            Error::Unsupported  => f.write_str("Unsupported data type"),
-            Error::IO           => f.write_str("IO error"),
-            Error::Overflow     => f.write_str("Integer overflow"),
+            Error::Unsupported(method) => write!(f, "Unsupported type for the altar format, passed to {}", method),
+            Error::IO(err)        => write!(f, "IO error while (de)serializing: {}", err),
+            Error::Overflow       => f.write_str("Integer overflow while (de)serializing"),
+            Error::Utf8 { valid_up_to, sequence } => write!(f, "Invalid UTF-8 sequence {:02x?} after {} valid bytes", sequence, valid_up_to),
+            Error::Eof            => f.write_str("Unexpected end of file"),
+            Error::Truncated { requested, remaining } => write!(f, "Tried to read {} bytes, but the input has {} left", requested, remaining),
+            Error::MissingVersion => f.write_str("Missing file version"),
+            Error::AllocationLimit { requested, limit } => write!(f, "Tried to allocate {} bytes, but the limit is {}", requested, limit),
+            Error::SectionDesync { section, expected, actual } => write!(f, "Section {} should begin at offset {}, but begins at {}", section, expected, actual),
+            Error::PositionMismatch { expected, actual } => write!(f, "Should be at offset {}, but is at {}", expected, actual),
+            Error::InvertedRange { start, end } => write!(f, "Range starts at {}, but ends at {}", start, end),
+            Error::ChecksumMismatch { expected, actual } => write!(f, "Checksum should be {:#x}, but is {:#x}", expected, actual),
+            Error::FooterMismatch { field, header, footer } => write!(f, "Footer {} should be {} as in the header, but is {}", field, header, footer),
+            Error::TrailingBytes { remaining: Some(remaining) } => write!(f, "{} bytes left after the value", remaining),
+            Error::TrailingBytes { remaining: None } => f.write_str("Data left after the value"),
+            Error::At { position, error } => write!(f, "{} at offset {}", error, position),
         }
     }
 
@@ -57,3 +227,54 @@ impl std::fmt::Display for Error {
 
 /// Base result type of this library.
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_every_variant() {
+        assert_eq!(Error::Message("custom".to_string()).to_string(), "custom");
+        assert_eq!(Error::Unsupported("deserialize_map").to_string(), "Unsupported type for the altar format, passed to deserialize_map");
+        let io = Error::IO(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe closed").into());
+        assert_eq!(io.to_string(), "IO error while (de)serializing: pipe closed");
+        assert_eq!(Error::Overflow.to_string(), "Integer overflow while (de)serializing");
+        assert_eq!(Error::Eof.to_string(), "Unexpected end of file");
+        assert_eq!(Error::Truncated { requested: 3, remaining: 1 }.to_string(), "Tried to read 3 bytes, but the input has 1 left");
+        assert_eq!(Error::TrailingBytes { remaining: Some(2) }.to_string(), "2 bytes left after the value");
+        assert_eq!(Error::TrailingBytes { remaining: None }.to_string(), "Data left after the value");
+        assert_eq!(Error::At { position: 3, error: Box::new(Error::Eof) }.to_string(), "Unexpected end of file at offset 3");
+    }
+
+    /// A [std::io::Write]r whose writes always fail.
+    struct BrokenWriter;
+
+    impl std::io::Write for BrokenWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::new(std::io::ErrorKind::BrokenPipe, "pipe closed"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn io_error_keeps_cause() {
+        let err = crate::to_writer(BrokenWriter, crate::VecI32(vec![1_i32])).err().unwrap();
+        match &err {
+            Error::IO(inner) => assert_eq!(inner.kind(), std::io::ErrorKind::BrokenPipe),
+            other => panic!("unexpected error {:?}", other),
+        };
+        assert!(err.to_string().contains("pipe closed"));
+        assert!(std::error::Error::source(&err).is_some());
+    }
+
+    #[test]
+    fn from_io_error() {
+        let eof: Error = std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into();
+        assert_eq!(eof, Error::Eof);
+        let denied: Error = std::io::Error::from(std::io::ErrorKind::PermissionDenied).into();
+        assert_eq!(denied, Error::IO(std::io::Error::from(std::io::ErrorKind::PermissionDenied).into()));
+    }
+}
@@ -1,20 +1,135 @@
+#[macro_use]
+mod macros;
+
 mod error;
 mod vec;
+mod world;
+mod map;
+mod types;
+mod checksum;
+pub mod mapped_id;
 mod ser;
 mod de;
+mod assertions;
 
 pub use ser::WriteSerializer;
 pub use ser::Serialize;
 pub use ser::to_writer;
+pub use ser::to_writer_counted;
+#[cfg(feature = "flate")]
+pub use ser::to_writer_deflate;
+#[cfg(feature = "flate")]
+pub use ser::to_writer_zlib;
+pub use ser::to_bytes;
 
 pub use de::ReadDeserializer;
+pub use de::SliceDeserializer;
+pub use de::Checkpoint;
+pub use de::DEFAULT_MAX_ALLOC;
+pub use de::StringEncoding;
+pub use de::LenKind;
 pub use de::Deserialize;
 pub use de::from_reader;
+pub use de::from_reader_exact;
+pub use de::from_reader_owned;
+#[cfg(feature = "flate")]
+pub use de::from_reader_deflate;
+#[cfg(feature = "flate")]
+pub use de::from_reader_zlib;
+pub use de::from_reader_with_progress;
+pub use de::from_bytes;
+pub use de::from_slice;
+pub use de::SectionValidator;
 
 pub use error::Error;
 pub use error::Result;
 
+pub use checksum::ChecksumAlgorithm;
+pub use checksum::ChecksummedReader;
+pub use checksum::ChecksummedWriter;
+
+pub use mapped_id::MappedId;
+
 pub use vec::VecI16Flags;
 pub use vec::VecULEB128;
+pub use vec::VecSLEB128;
+pub use vec::MapULEB128;
+pub use vec::RleVec;
+pub use vec::RleCount;
 pub use vec::VecI16;
 pub use vec::VecI32;
+pub use vec::VecU8;
+pub use vec::VecU32;
+pub use vec::VecI64;
+pub use vec::VecI32F32;
+
+pub use types::Leb128U64;
+pub use types::VersionedU8;
+pub use types::BoolGrid2D;
+pub use types::BlobList;
+pub use types::Tlv;
+pub use types::RangeI32;
+pub use types::VersionedArray;
+pub use types::BitmaskU64;
+pub use types::Color;
+pub use types::OptionalColor;
+pub use types::FixedPoint;
+pub use types::NullableStringI32;
+pub use types::DeltaF32Series;
+pub use types::PackedNBit;
+pub use types::TrailingOption;
+pub use types::Rect;
+pub use types::DateTimeKind;
+pub use types::DateTime;
+pub use types::CharMap;
+pub use types::Vector2;
+pub use types::DeltaPosList;
+pub use types::OptionBool;
+pub use types::ExtraHeader;
+pub use types::BoolArray;
+pub use types::Guid;
+
+pub use world::BOSS_FLAGS_VERSIONS;
+pub use world::CREATIVE_POWER_SLIDERS;
+pub use world::CREATIVE_POWERS_END;
+pub use world::BossFlags;
+pub use world::OreTiers;
+pub use world::PartyData;
+pub use world::ShopInventory;
+pub use world::TravelingMerchantInventory;
+pub use world::MoonData;
+pub use world::InvasionData;
+pub use world::GenInfo;
+pub use world::TileShape;
+pub use world::TownNpc;
+pub use world::CreativePower;
+pub use world::CreativePowers;
+pub use world::TileCounts;
+pub use world::TimeState;
+pub use world::LogicSensor;
+pub use world::WeightedPlate;
+pub use world::WorldNames;
+pub use world::Backgrounds;
+pub use world::GAME_MODE_VERSION;
+pub use world::Difficulty;
+pub use world::LayerDepths;
+pub use world::NamedKillCounts;
+pub use world::WorldFooter;
+pub use world::PaintColor;
+pub use world::ItemStack;
+pub use world::ItemFrame;
+pub use world::WeaponRack;
+pub use world::SANDSTORM_VERSION;
+pub use world::LANTERN_NIGHT_VERSION;
+pub use world::Sandstorm;
+pub use world::LanternNight;
+pub use world::EventTimers;
+pub use world::SHIMMER_VERSION;
+pub use world::ShimmerPosition;
+pub use world::ShimmerData;
+
+pub use map::RELOGIC_MAGIC;
+pub use map::MAP_FILE_TYPE;
+pub use map::MapTile;
+pub use map::MapHeader;
+pub use map::MapTileColor;
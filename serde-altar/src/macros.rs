@@ -0,0 +1,125 @@
+/// Generate a [Vec] newtype prefixed by its size as the integer type `$prefix`, along with its visitor and its (de)serialization impls.
+///
+/// `$seq` is the name of the newtype `struct` recognized by the deserializer, which has to read the size prefix before visiting the values.
+///
+/// If `hooks(...)` are given, the custom `Serialize` and `Deserialize` impls go through the given [crate::ser::Serializer], [crate::de::Deserializer] and [crate::de::Visitor] methods; otherwise, they forward to the `serde` ones.
+macro_rules! impl_framed_vec {
+    ($name:ident, $visitor:ident, $prefix:ty, $seq:path) => {
+        impl_framed_vec!(@common $name, $visitor, $prefix, $seq);
+
+        impl<T> crate::ser::Serialize for $name<T> where T: serde::ser::Serialize {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+                serde::ser::Serialize::serialize(self, serializer)
+            }
+        }
+
+        impl<'de, T> crate::de::Visitor<'de> for $visitor<T> where T: crate::de::Deserialize<'de, T> {}
+    };
+
+    ($name:ident, $visitor:ident, $prefix:ty, $seq:path, hooks($serialize_hook:ident, $deserialize_hook:ident, $visit_hook:ident)) => {
+        impl_framed_vec!(@common $name, $visitor, $prefix, $seq);
+
+        impl<T> crate::ser::Serialize for $name<T> where T: serde::ser::Serialize {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+                use serde::ser::SerializeSeq;
+                let len = <$prefix>::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom(concat!("Vec length does not fit in a ", stringify!($prefix))))?;
+                let mut seq = serializer.$serialize_hook(len)?;
+                for element in &self.0 {
+                    seq.serialize_element(&element)?;
+                };
+                seq.end()
+            }
+        }
+
+        impl<'de, T> crate::de::Deserialize<'de, T> for $name<T> where T: serde::de::Deserialize<'de> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: crate::de::Deserializer<'de>, T: crate::de::Deserialize<'de, T> {
+                deserializer.$deserialize_hook($visitor::<T>(std::marker::PhantomData))
+            }
+        }
+
+        impl<'de, T> crate::de::Visitor<'de> for $visitor<T> where T: crate::de::Deserialize<'de, T> {
+            fn $visit_hook<S: serde::de::SeqAccess<'de>>(self, seq: S) -> Result<Self::Value, S::Error> {
+                serde::de::Visitor::visit_seq(self, seq)
+            }
+
+            fn element_size(&self) -> usize {
+                std::mem::size_of::<T>()
+            }
+        }
+    };
+
+    (@common $name:ident, $visitor:ident, $prefix:ty, $seq:path) => {
+        #[doc = concat!("A [", stringify!($prefix), "]-sized [Vec] serialized as a sequence of `T`.")]
+        pub struct $name<T> (pub Vec<T>);
+
+        #[doc = concat!("Visitor for [", stringify!($name), "], containing `T`s.")]
+        pub struct $visitor<T> (pub std::marker::PhantomData<T>);
+
+        impl<T> serde::ser::Serialize for $name<T> where T: serde::ser::Serialize {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+                use serde::ser::SerializeTuple;
+                // The size is stored as the first element of the sequence.
+                let len = <$prefix>::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom(concat!("Vec length does not fit in a ", stringify!($prefix))))?;
+                let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+                tuple.serialize_element(&len)?;
+                for element in &self.0 {
+                    tuple.serialize_element(element)?;
+                };
+                tuple.end()
+            }
+        }
+
+        impl<'de, T> serde::Deserialize<'de> for $name<T> where T: serde::de::Deserialize<'de> {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::de::Deserializer<'de> {
+                deserializer.deserialize_newtype_struct($seq, $visitor::<T>(std::marker::PhantomData))
+            }
+        }
+
+        impl<'de, T> serde::de::Visitor<'de> for $visitor<T> where T: serde::de::Deserialize<'de> {
+            type Value = $name<T>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str(concat!("a ", stringify!($prefix), "-sized list"))
+            }
+
+            fn visit_seq<S: serde::de::SeqAccess<'de>>(self, mut seq: S) -> Result<Self::Value, S::Error> {
+                let mut inner_vec: Vec<T> = vec![];
+                while let Some(element) = seq.next_element()? {
+                    inner_vec.push(element);
+                }
+                Ok($name(inner_vec))
+            }
+        }
+    };
+}
+
+/// Implement [crate::ser::Serialize] for types whose custom serialization is the same as their `serde` one, by forwarding to it.
+///
+/// Generic types are given one at a time, prefixed by their generic parameters: either `const` parameters or type parameters, for which the `serde` impl is required.
+macro_rules! impl_serialize_forward {
+    (<$(const $param:ident: $kind:ty),+> $name:ty) => {
+        impl<$(const $param: $kind),+> crate::ser::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+                serde::ser::Serialize::serialize(self, serializer)
+            }
+        }
+    };
+
+    (<$($param:ident),+> $name:ty) => {
+        impl<$($param),+> crate::ser::Serialize for $name where $name: serde::ser::Serialize {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+                serde::ser::Serialize::serialize(self, serializer)
+            }
+        }
+    };
+
+    ($($name:ty),+ $(,)?) => {
+        $(
+            impl crate::ser::Serialize for $name {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+                    serde::ser::Serialize::serialize(self, serializer)
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,194 @@
+/// Bit of a map tile header signaling that the light level follows the tile type.
+pub(crate) const MAP_TILE_HAS_LIGHT: u8 = 0b0000_0001;
+
+/// Bit of a map tile header signaling that the tile type is stored as a [u16] instead of a [u8].
+pub(crate) const MAP_TILE_WIDE_TYPE: u8 = 0b0000_0010;
+
+/// Amount of bits to shift a map tile header by to obtain the width of its repeat count: `0` for none, `1` for [u8], `2` for [u16].
+pub(crate) const MAP_TILE_REPEAT_SHIFT: u8 = 6;
+
+/// Magic bytes identifying the files written by Re-Logic, preceding the file type.
+pub const RELOGIC_MAGIC: [u8; 7] = *b"relogic";
+
+/// File type byte of map (`.map`) files.
+pub const MAP_FILE_TYPE: u8 = 1;
+
+/// A tile of a map (`.map`) file, as it was last seen by the player.
+///
+/// Map tiles are run-length encoded: each run starts with a header byte, followed by the tile type, the light level if not full, and the amount of times the tile is repeated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapTile {
+    /// The type of the tile, used to pick its color from the palette.
+    pub tile_type: u16,
+
+    /// The light level of the tile, where [u8::MAX] is full light.
+    pub light: u8,
+}
+
+/// The header of a map (`.map`) file, serialized as its version, [RELOGIC_MAGIC], its file type, its revision and its favorite flags.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MapHeader {
+    /// The version of the game which wrote the file.
+    pub version: i32,
+
+    /// The type of the file, which is [MAP_FILE_TYPE] for maps.
+    pub file_type: u8,
+
+    /// The amount of times the file was saved.
+    pub revision: u32,
+
+    /// The favorite flags of the file, whose lowest bit tells whether it is a favorite.
+    pub favorites: u64,
+}
+
+impl MapHeader {
+    /// Whether the file was marked as a favorite.
+    pub fn is_favorite(&self) -> bool {
+        self.favorites & 1 != 0
+    }
+}
+
+/// A single map tile whose type fits in a [u8], serialized as a map tile run of length one.
+///
+/// Like in the run-length encoding of [MapTile]s, the light level is only stored if it is not full: the header byte has [MAP_TILE_HAS_LIGHT] set if it follows the color index, and otherwise the light level is [u8::MAX].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MapTileColor {
+    /// The index of the color of the tile in the palette.
+    pub color: u8,
+
+    /// The light level of the tile, where [u8::MAX] is full light.
+    pub light: u8,
+}
+
+impl MapTileColor {
+    /// Get the color and light of the given tile, if its type fits in a [u8].
+    pub fn from_tile(tile: MapTile) -> Option<Self> {
+        let color = u8::try_from(tile.tile_type).ok()?;
+        Some(MapTileColor { color, light: tile.light })
+    }
+}
+
+impl From<MapTileColor> for MapTile {
+    fn from(value: MapTileColor) -> Self {
+        MapTile { tile_type: value.color.into(), light: value.light }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SliceDeserializer;
+    use crate::WriteSerializer;
+
+    #[test]
+    fn map_tiles_roundtrip() {
+        let dirt = MapTile { tile_type: 0, light: u8::MAX };
+        let dim_stone = MapTile { tile_type: 1, light: 100 };
+        let wide = MapTile { tile_type: 600, light: u8::MAX };
+        let mut tiles = vec![dirt; 3];
+        tiles.push(dim_stone);
+        tiles.extend(std::iter::repeat(wide).take(300));
+        tiles.push(dirt);
+
+        let mut ser = WriteSerializer::new(vec![]);
+        ser.serialize_map_tiles(&tiles).unwrap();
+        let bytes = ser.into_inner();
+        // Four runs: a repeated narrow tile, a dim tile, a long wide run and a single tile.
+        assert_eq!(bytes.len(), (1 + 1 + 1) + (1 + 1 + 1) + (1 + 2 + 2) + (1 + 1));
+
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        assert_eq!(de.deserialize_map_tiles(tiles.len()).unwrap(), tiles);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn map_tiles_run_past_count() {
+        let tiles = vec![MapTile { tile_type: 5, light: u8::MAX }; 4];
+        let mut ser = WriteSerializer::new(vec![]);
+        ser.serialize_map_tiles(&tiles).unwrap();
+        let bytes = ser.into_inner();
+
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        assert_eq!(de.deserialize_map_tiles(3).err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Overflow) }));
+    }
+
+    #[test]
+    fn map_header_decode() {
+        let mut bytes = 279_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"relogic");
+        bytes.push(MAP_FILE_TYPE);
+        bytes.extend_from_slice(&42_u32.to_le_bytes());
+        bytes.extend_from_slice(&1_u64.to_le_bytes());
+
+        let header: MapHeader = crate::from_slice(&bytes).unwrap();
+        assert_eq!(header.version, 279);
+        assert_eq!(header.file_type, MAP_FILE_TYPE);
+        assert_eq!(header.revision, 42);
+        assert!(header.is_favorite());
+        assert_eq!(crate::to_bytes(header).unwrap(), bytes);
+    }
+
+    #[test]
+    fn map_header_wrong_magic() {
+        let mut bytes = 279_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(b"xindong");
+        bytes.push(MAP_FILE_TYPE);
+        bytes.extend_from_slice(&[0; 12]);
+        assert!(crate::from_slice::<MapHeader>(&bytes).is_err());
+    }
+
+    #[test]
+    fn tiles_row_major_transpose() {
+        // Columns are stored one after the other, each from top to bottom.
+        let (width, height) = (3, 2);
+        let columns: Vec<MapTile> = (0..width).flat_map(|x| (0..height).map(move |y| MapTile { tile_type: x * 10 + y, light: u8::MAX })).collect();
+        let mut ser = WriteSerializer::new(vec![]);
+        ser.serialize_map_tiles(&columns).unwrap();
+        let bytes = ser.into_inner();
+
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        let rows = de.decode_tiles_row_major(width as usize, height as usize).unwrap();
+        assert!(de.remaining().is_empty());
+        let types: Vec<u16> = rows.iter().map(|tile| tile.tile_type).collect();
+        assert_eq!(types, [0, 10, 20, 1, 11, 21]);
+        for y in 0..height {
+            for x in 0..width {
+                assert_eq!(rows[(y * width + x) as usize].tile_type, x * 10 + y);
+            }
+        }
+    }
+
+    #[test]
+    fn tiles_row_major_too_large() {
+        let mut de = SliceDeserializer::from_slice(&[]);
+        assert_eq!(de.decode_tiles_row_major(usize::MAX, 2).err(), Some(crate::Error::Overflow));
+    }
+
+    #[test]
+    fn map_tile_color_light_byte() {
+        let bright = MapTileColor { color: 7, light: u8::MAX };
+        assert_eq!(crate::to_bytes(bright).unwrap(), [0, 7]);
+        assert_eq!(crate::from_slice::<MapTileColor>(&[0, 7]).unwrap(), bright);
+
+        let dim = MapTileColor { color: 7, light: 40 };
+        assert_eq!(crate::to_bytes(dim).unwrap(), [MAP_TILE_HAS_LIGHT, 7, 40]);
+        assert_eq!(crate::from_slice::<MapTileColor>(&[MAP_TILE_HAS_LIGHT, 7, 40]).unwrap(), dim);
+        assert!(crate::from_slice::<MapTileColor>(&[MAP_TILE_HAS_LIGHT, 7]).is_err());
+        assert!(crate::from_slice::<MapTileColor>(&[MAP_TILE_WIDE_TYPE, 7, 0]).is_err());
+
+        // The same bytes decode as a single tile of the run-length encoding.
+        let mut de = SliceDeserializer::from_slice(&[MAP_TILE_HAS_LIGHT, 7, 40]);
+        let tiles = de.deserialize_map_tiles(1).unwrap();
+        assert_eq!(tiles, [MapTile::from(dim)]);
+        assert_eq!(MapTileColor::from_tile(tiles[0]), Some(dim));
+        assert_eq!(MapTileColor::from_tile(MapTile { tile_type: 600, light: 0 }), None);
+    }
+
+    #[test]
+    fn map_tiles_truncated() {
+        // A narrow tile, followed by a wide tile missing the high byte of its type.
+        let bytes = [0, 5, MAP_TILE_WIDE_TYPE, 0x58];
+        let mut de = SliceDeserializer::from_slice(&bytes);
+        assert_eq!(de.deserialize_map_tiles(2).err(), Some(crate::Error::At { position: 2, error: Box::new(crate::Error::Eof) }));
+    }
+}
@@ -0,0 +1,87 @@
+//! (De)serialization of enums stored as an arbitrary id per variant, to be used with `#[serde(with = "serde_altar::mapped_id")]`.
+//!
+//! The mapping between variants and ids is specified by implementing [MappedId].
+
+/// Enum stored as an arbitrary id per variant, which doesn't have to follow the order of the variants.
+pub trait MappedId: Sized {
+    /// The integer type the id is stored as.
+    type Id: serde::ser::Serialize + serde::de::DeserializeOwned + Copy + std::fmt::Display;
+
+    /// The id the variant is stored as.
+    fn to_id(&self) -> Self::Id;
+
+    /// The variant stored as the given `id`, if there is one.
+    fn from_id(id: Self::Id) -> Option<Self>;
+}
+
+/// Serialize the id of the given variant.
+pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error> where T: MappedId, S: serde::ser::Serializer {
+    serde::ser::Serialize::serialize(&value.to_id(), serializer)
+}
+
+/// Deserialize an id, and find out which variant it stands for.
+///
+/// Fails if no variant is stored as the id.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error> where T: MappedId, D: serde::de::Deserializer<'de> {
+    let id = <T::Id as serde::de::Deserialize>::deserialize(deserializer)?;
+    T::from_id(id).ok_or_else(|| serde::de::Error::custom(format!("Unknown id {}", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Biome {
+        Forest,
+        Desert,
+        Jungle,
+    }
+
+    impl MappedId for Biome {
+        type Id = i16;
+
+        fn to_id(&self) -> i16 {
+            match self {
+                Biome::Forest => 5,
+                Biome::Desert => 17,
+                Biome::Jungle => 100,
+            }
+        }
+
+        fn from_id(id: i16) -> Option<Self> {
+            match id {
+                5 => Some(Biome::Forest),
+                17 => Some(Biome::Desert),
+                100 => Some(Biome::Jungle),
+                _ => None,
+            }
+        }
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Spawn {
+        #[serde(with = "crate::mapped_id")]
+        biome: Biome,
+        x: u8,
+    }
+
+    impl_serialize_forward!(Spawn);
+
+    #[test]
+    fn mapped_id_roundtrip() {
+        for (biome, id) in [(Biome::Forest, 5_i16), (Biome::Desert, 17), (Biome::Jungle, 100)] {
+            let bytes = crate::to_bytes(Spawn { biome, x: 3 }).unwrap();
+            let mut expected = id.to_le_bytes().to_vec();
+            expected.push(3);
+            assert_eq!(bytes, expected);
+            assert_eq!(crate::from_slice::<Spawn>(&bytes).unwrap(), Spawn { biome, x: 3 });
+        }
+    }
+
+    #[test]
+    fn mapped_id_unknown() {
+        let bytes = [6, 0, 3];
+        assert_eq!(crate::from_slice::<Spawn>(&bytes).err(), Some(crate::Error::Message("Unknown id 6".to_string())));
+    }
+}
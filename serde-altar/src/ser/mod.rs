@@ -6,9 +6,37 @@ pub use serializer::Serializer;
 pub use serializer::WriteSerializer;
 
 
-/// Serialize any [Serialize]able struct using a [Write]r as a destination.
+/// Serialize any [Serialize]able struct using a [Write]r as a destination, flushing it before returning it.
 pub fn to_writer<W, T>(writer: W, value: T) -> crate::Result<W> where W: std::io::Write, T: Serialize {
-    let mut ser = WriteSerializer { writer };
+    let (writer, _bytes_written) = to_writer_counted(writer, value)?;
+    Ok(writer)
+}
+
+/// Serialize any [Serialize]able struct using a [Write]r as a destination, flushing it before returning it along with the amount of bytes written.
+pub fn to_writer_counted<W, T>(writer: W, value: T) -> crate::Result<(W, u64)> where W: std::io::Write, T: Serialize {
+    let mut ser = WriteSerializer::new(writer);
     Serialize::serialize(&value, &mut ser)?;
-    Ok(ser.writer)
+    let bytes_written = ser.bytes_written();
+    Ok((ser.finish()?, bytes_written))
+}
+
+/// Serialize any [Serialize]able struct as a raw deflate stream using a [Write]r as a destination, finishing the stream before returning the writer.
+#[cfg(feature = "flate")]
+pub fn to_writer_deflate<W, T>(writer: W, value: T) -> crate::Result<W> where W: std::io::Write, T: Serialize {
+    let encoder = flate2::write::DeflateEncoder::new(writer, flate2::Compression::default());
+    let encoder = to_writer(encoder, value)?;
+    Ok(encoder.finish()?)
+}
+
+/// Serialize any [Serialize]able struct as a zlib stream using a [Write]r as a destination, finishing the stream before returning the writer.
+#[cfg(feature = "flate")]
+pub fn to_writer_zlib<W, T>(writer: W, value: T) -> crate::Result<W> where W: std::io::Write, T: Serialize {
+    let encoder = flate2::write::ZlibEncoder::new(writer, flate2::Compression::default());
+    let encoder = to_writer(encoder, value)?;
+    Ok(encoder.finish()?)
+}
+
+/// Serialize any [Serialize]able struct into a new [Vec] of bytes.
+pub fn to_bytes<T>(value: T) -> crate::Result<Vec<u8>> where T: Serialize {
+    to_writer(vec![], value)
 }
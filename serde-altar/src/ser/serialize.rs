@@ -1,35 +1,102 @@
 use serde::ser::SerializeSeq;
+use serde::ser::SerializeTuple;
 use crate::VecI16Flags;
 use crate::VecULEB128;
-use crate::VecI16;
-use crate::VecI32;
+use crate::VecSLEB128;
+use crate::RleVec;
+use crate::RleCount;
+use crate::MapULEB128;
+use crate::BossFlags;
+use crate::Leb128U64;
+use crate::VersionedU8;
+use crate::BoolGrid2D;
+use crate::BlobList;
+use crate::OreTiers;
+use crate::PartyData;
+use crate::ShopInventory;
+use crate::MoonData;
+use crate::InvasionData;
+use crate::GenInfo;
+use crate::Tlv;
+use crate::RangeI32;
+use crate::VersionedArray;
+use crate::FixedPoint;
+use crate::DeltaF32Series;
+use crate::PackedNBit;
+use crate::BitmaskU64;
+use crate::TileShape;
+use crate::Color;
+use crate::OptionalColor;
+use crate::TrailingOption;
+use crate::OptionBool;
+use crate::TownNpc;
+use crate::CreativePower;
+use crate::CreativePowers;
+use crate::TileCounts;
+use crate::BoolArray;
+use crate::TimeState;
+use crate::NullableStringI32;
+use crate::MapHeader;
+use crate::LogicSensor;
+use crate::WeightedPlate;
+use crate::WorldNames;
+use crate::VecI32F32;
+use crate::Rect;
+use crate::Backgrounds;
+use crate::DateTime;
+use crate::CharMap;
+use crate::Vector2;
+use crate::Difficulty;
+use crate::DeltaPosList;
+use crate::LayerDepths;
+use crate::ExtraHeader;
+use crate::NamedKillCounts;
+use crate::WorldFooter;
+use crate::PaintColor;
+use crate::ItemStack;
+use crate::ItemFrame;
+use crate::WeaponRack;
+use crate::Guid;
+use crate::Sandstorm;
+use crate::LanternNight;
+use crate::EventTimers;
+use crate::MapTileColor;
+use crate::ShimmerPosition;
+use crate::ShimmerData;
 
 pub trait Serialize : serde::ser::Serialize {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer;
 }
 
 impl serde::ser::Serialize for VecI16Flags {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
-        Err(serde::ser::Error::custom("Cannot serialize VecI16Flags with the serde Serializer"))
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The size is the amount of bits, which are then packed 8 per byte.
+        let len = i16::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("Vec length does not fit in a i16"))?;
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&len)?;
+        tuple.serialize_element(&PackedBits(&self.0))?;
+        tuple.end()
     }
 }
 
 impl Serialize for VecI16Flags {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
-        let bit_len = i16::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("Vec length does not fit in a i16"))?;
-        let should_add_one = (bit_len % 8) != 0;
-        let len = (bit_len / 8) + if should_add_one { 1 } else { 0 };
+        let len = i16::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("Vec length does not fit in a i16"))?;
         let mut seq = serializer.serialize_vec_i16flags(len)?;
-        for element in &self.0 {
-            seq.serialize_element(&element)?;
-        };
+        seq.serialize_element(&PackedBits(&self.0))?;
         seq.end()
     }
 }
 
-impl<T> serde::ser::Serialize for VecULEB128<T> {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
-        Err(serde::ser::Error::custom("Cannot serialize VecULEB128 with the serde Serializer"))
+impl<T> serde::ser::Serialize for VecULEB128<T> where T: serde::ser::Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The size is stored as the first element of the sequence.
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&Leb128U64(self.0.len() as u64))?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
     }
 }
 
@@ -44,16 +111,23 @@ impl<T> Serialize for VecULEB128<T> where T: serde::ser::Serialize {
     }
 }
 
-impl<T> serde::ser::Serialize for VecI16<T> {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
-        Err(serde::ser::Error::custom("Cannot serialize VecI16 with the serde Serializer"))
+impl<T> serde::ser::Serialize for VecSLEB128<T> where T: serde::ser::Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i64::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("VecSLEB128 length does not fit in a i64"))?;
+        // The size is stored as the first element of the sequence.
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&Sleb128(len))?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
     }
 }
 
-impl<T> Serialize for VecI16<T> where T: serde::ser::Serialize {
+impl<T> Serialize for VecSLEB128<T> where T: serde::ser::Serialize {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
-        let len = i16::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("Vec length does not fit in a i16"))?;
-        let mut seq = serializer.serialize_vec_i16(len)?;
+        let len = i64::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("VecSLEB128 length does not fit in a i64"))?;
+        let mut seq = serializer.serialize_vec_sleb128(len)?;
         for element in &self.0 {
             seq.serialize_element(&element)?;
         };
@@ -61,20 +135,624 @@ impl<T> Serialize for VecI16<T> where T: serde::ser::Serialize {
     }
 }
 
-impl<T> serde::ser::Serialize for VecI32<T> {
-    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
-        Err(serde::ser::Error::custom("Cannot serialize VecI32 with the serde Serializer"))
+impl<T, C> RleVec<T, C> where T: PartialEq, C: RleCount {
+    /// Split the values into runs of equal values, each at most `C::MAX + 1` long, and return the first value of each run along with its repeat count.
+    fn runs(&self) -> Vec<(&T, C)> {
+        let mut runs: Vec<(&T, C)> = vec![];
+        let mut index = 0;
+        while index < self.0.len() {
+            let value = &self.0[index];
+            let repeat = self.0[index + 1..].iter().take(C::MAX).take_while(|other| *other == value).count();
+            // The repeat count is at most `C::MAX`, so it always fits.
+            let count = C::try_from(repeat).unwrap_or_else(|_err| unreachable!());
+            runs.push((value, count));
+            index += repeat + 1;
+        }
+        runs
+    }
+}
+
+impl<T, C> serde::ser::Serialize for RleVec<T, C> where T: serde::ser::Serialize + PartialEq, C: RleCount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("RleVec length does not fit in a i32"))?;
+        let runs = self.runs();
+        // The expanded size is stored as the first element of the sequence.
+        let mut tuple = serializer.serialize_tuple(runs.len() * 2 + 1)?;
+        tuple.serialize_element(&len)?;
+        for (value, count) in &runs {
+            tuple.serialize_element(value)?;
+            tuple.serialize_element(count)?;
+        };
+        tuple.end()
+    }
+}
+
+impl<T, C> Serialize for RleVec<T, C> where T: serde::ser::Serialize + PartialEq, C: RleCount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("RleVec length does not fit in a i32"))?;
+        let mut seq = serializer.serialize_rle(len)?;
+        for (value, count) in self.runs() {
+            seq.serialize_element(value)?;
+            seq.serialize_element(&count)?;
+        };
+        seq.end()
+    }
+}
+
+impl<K, V> serde::ser::Serialize for MapULEB128<K, V> where K: serde::ser::Serialize, V: serde::ser::Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The size is stored as the first element of the sequence, followed by the keys and values in order.
+        let mut tuple = serializer.serialize_tuple(self.0.len() * 2 + 1)?;
+        tuple.serialize_element(&Leb128U64(self.0.len() as u64))?;
+        for (key, value) in &self.0 {
+            tuple.serialize_element(key)?;
+            tuple.serialize_element(value)?;
+        };
+        tuple.end()
     }
 }
 
-impl<T> Serialize for VecI32<T> where T: serde::ser::Serialize {
+impl<K, V> Serialize for MapULEB128<K, V> where K: serde::ser::Serialize, V: serde::ser::Serialize {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: crate::ser::Serializer {
+        use serde::ser::SerializeMap;
+        let mut map = serializer.serialize_map_uleb128(self.0.len())?;
+        for (key, value) in &self.0 {
+            map.serialize_entry(key, value)?;
+        };
+        map.end()
+    }
+}
+
+impl serde::ser::Serialize for BossFlags {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // Only the stored flags are written back, so that the file keeps its version.
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for Leb128U64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // ULEB128 integers are stored as the sequence of their encoded bytes.
+        let mut bytes: Vec<u8> = vec![];
+        leb128::write::unsigned(&mut bytes, self.0).map_err(|_err| serde::ser::Error::custom("Could not encode ULEB128"))?;
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in &bytes {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for VersionedU8 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // Files are always written in the latest version, so the value is always present.
+        serializer.serialize_u8(self.0)
+    }
+}
+
+/// A sequence of `bool`s serialized as bytes containing 8 of them each, least significant first.
+struct PackedBits<'a> (&'a [bool]);
+
+impl serde::ser::Serialize for PackedBits<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let bytes: Vec<u8> = self.0.chunks(8)
+            .map(|chunk| chunk.iter().enumerate().fold(0_u8, |byte, (index, bit)| byte | (u8::from(*bit) << index)))
+            .collect();
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in &bytes {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for VecI32F32 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
         let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("Vec length does not fit in a i32"))?;
-        let mut seq = serializer.serialize_vec_i32(len)?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&len)?;
         for element in &self.0 {
-            seq.serialize_element(&element)?;
+            tuple.serialize_element(element)?;
         };
-        seq.end()
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for BoolGrid2D {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let count = i64::from(self.width) * i64::from(self.height);
+        if usize::try_from(count).ok() != Some(self.bits.len()) {
+            return Err(serde::ser::Error::custom("BoolGrid2D bits do not match its dimensions"))
+        }
+        let mut tuple = serializer.serialize_tuple(3)?;
+        tuple.serialize_element(&self.width)?;
+        tuple.serialize_element(&self.height)?;
+        tuple.serialize_element(&PackedBits(&self.bits))?;
+        tuple.end()
+    }
+}
+
+/// A blob of bytes serialized as its size as an ULEB128, followed by its contents.
+struct Blob<'a> (&'a [u8]);
+
+impl serde::ser::Serialize for Blob<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&Leb128U64(self.0.len() as u64))?;
+        for byte in self.0 {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for BlobList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("BlobList length does not fit in a i32"))?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&len)?;
+        for element in &self.0 {
+            tuple.serialize_element(&Blob(element))?;
+        };
+        tuple.end()
+    }
+}
+
+impl<const N: usize> serde::ser::Serialize for ShopInventory<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for Tlv {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = u32::try_from(self.value.len()).map_err(|_err| serde::ser::Error::custom("Tlv value length does not fit in a u32"))?;
+        let mut tuple = serializer.serialize_tuple(self.value.len() + 2)?;
+        tuple.serialize_element(&self.tag)?;
+        tuple.serialize_element(&len)?;
+        for byte in &self.value {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for RangeI32 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.0.start)?;
+        tuple.serialize_element(&self.0.end)?;
+        tuple.end()
+    }
+}
+
+impl<const N: usize, const COUNTED_SINCE: i32> serde::ser::Serialize for VersionedArray<N, COUNTED_SINCE> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        match self.counted {
+            // Counted lists are prefixed with their length.
+            true => {
+                let len = i32::try_from(self.values.len()).map_err(|_err| serde::ser::Error::custom("VersionedArray is too long"))?;
+                let mut tuple = serializer.serialize_tuple(self.values.len() + 1)?;
+                tuple.serialize_element(&len)?;
+                for element in &self.values {
+                    tuple.serialize_element(element)?;
+                };
+                tuple.end()
+            },
+            // Fixed lists must have exactly `N` values.
+            false => {
+                if self.values.len() != N {
+                    return Err(serde::ser::Error::custom("Fixed VersionedArray has the wrong length"));
+                }
+                let mut tuple = serializer.serialize_tuple(N)?;
+                for element in &self.values {
+                    tuple.serialize_element(element)?;
+                };
+                tuple.end()
+            },
+        }
+    }
+}
+
+impl<const N: usize> serde::ser::Serialize for BitmaskU64<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let _unused_bits = BitmaskU64::<N>::UNUSED_BITS;
+        let mask = self.bits.iter().enumerate().fold(0u64, |mask, (index, bit)| mask | ((*bit as u64) << index));
+        serializer.serialize_u64(mask)
+    }
+}
+
+impl serde::ser::Serialize for TileShape {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        serializer.serialize_u8(self.value())
+    }
+}
+
+impl<T> serde::ser::Serialize for OptionBool<T> where T: serde::ser::Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        match &self.0 {
+            Some(value) => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&true)?;
+                tuple.serialize_element(value)?;
+                tuple.end()
+            },
+            None => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<T> serde::ser::Serialize for TrailingOption<T> where T: serde::ser::Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.value)?;
+        tuple.serialize_element(&self.present)?;
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for OptionalColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        match &self.0 {
+            Some(color) => {
+                let mut tuple = serializer.serialize_tuple(2)?;
+                tuple.serialize_element(&true)?;
+                tuple.serialize_element(color)?;
+                tuple.end()
+            },
+            None => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl serde::ser::Serialize for CreativePower {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(2)?;
+        match self {
+            CreativePower::Toggle { id, enabled } => {
+                tuple.serialize_element(id)?;
+                tuple.serialize_element(enabled)?;
+            },
+            CreativePower::Slider { id, value } => {
+                tuple.serialize_element(id)?;
+                tuple.serialize_element(value)?;
+            },
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for CreativePowers {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The end of the list is marked by an additional id.
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.serialize_element(&crate::CREATIVE_POWERS_END)?;
+        tuple.end()
+    }
+}
+
+impl<const SCALE: i64> serde::ser::Serialize for FixedPoint<SCALE> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let scaled = (self.0 * SCALE as f64).round();
+        if !(i32::MIN as f64..=i32::MAX as f64).contains(&scaled) {
+            return Err(serde::ser::Error::custom("FixedPoint does not fit in a i32"));
+        }
+        serializer.serialize_i32(scaled as i32)
+    }
+}
+
+impl<const N: usize> serde::ser::Serialize for TileCounts<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        if self.0.len() != N {
+            return Err(serde::ser::Error::custom("TileCounts has the wrong length"));
+        }
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
+    }
+}
+
+impl<const N: usize> serde::ser::Serialize for BoolArray<N> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(N)?;
+        for element in &self.0 {
+            tuple.serialize_element(element)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for NullableStringI32 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        match &self.0 {
+            Some(string) => {
+                let len = i32::try_from(string.len()).map_err(|_err| serde::ser::Error::custom("String length does not fit in a i32"))?;
+                let mut tuple = serializer.serialize_tuple(string.len() + 1)?;
+                tuple.serialize_element(&len)?;
+                for byte in string.as_bytes() {
+                    tuple.serialize_element(byte)?;
+                };
+                tuple.end()
+            },
+            // Null strings are stored as just their length.
+            None => serializer.serialize_i32(-1),
+        }
+    }
+}
+
+impl serde::ser::Serialize for MapHeader {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(5)?;
+        tuple.serialize_element(&self.version)?;
+        tuple.serialize_element(&crate::RELOGIC_MAGIC)?;
+        tuple.serialize_element(&self.file_type)?;
+        tuple.serialize_element(&self.revision)?;
+        tuple.serialize_element(&self.favorites)?;
+        tuple.end()
+    }
+}
+
+impl<const SCALE: i64> serde::ser::Serialize for DeltaF32Series<SCALE> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("DeltaF32Series length does not fit in a i32"))?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() + 1)?;
+        tuple.serialize_element(&len)?;
+        if let Some((first, rest)) = self.0.split_first() {
+            tuple.serialize_element(first)?;
+            // Deltas are taken from the reconstructed values, so that rounding errors don't accumulate.
+            let mut previous = *first as f64;
+            for value in rest {
+                let delta = ((*value as f64 - previous) * SCALE as f64).round();
+                if !(i16::MIN as f64..=i16::MAX as f64).contains(&delta) {
+                    return Err(serde::ser::Error::custom("DeltaF32Series delta does not fit in a i16"));
+                }
+                tuple.serialize_element(&(delta as i16))?;
+                previous += delta / SCALE as f64;
+            }
+        }
+        tuple.end()
+    }
+}
+
+impl<const BITS: u8> serde::ser::Serialize for PackedNBit<BITS> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        if !matches!(BITS, 1 | 2 | 4 | 8) {
+            return Err(serde::ser::Error::custom("PackedNBit width is not a power of two no greater than 8"))
+        }
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("PackedNBit length does not fit in a i32"))?;
+        if self.0.iter().any(|value| u16::from(*value) >> BITS != 0) {
+            return Err(serde::ser::Error::custom("PackedNBit value does not fit in its width"))
+        }
+        let per_byte = usize::from(8 / BITS);
+        let bytes: Vec<u8> = self.0.chunks(per_byte)
+            .map(|chunk| chunk.iter().enumerate().fold(0_u8, |byte, (index, value)| byte | (value << (index * usize::from(BITS)))))
+            .collect();
+        let mut tuple = serializer.serialize_tuple(bytes.len() + 1)?;
+        tuple.serialize_element(&len)?;
+        for byte in &bytes {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
     }
 }
 
+impl serde::ser::Serialize for CharMap {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // `char`s are not supported by the format, so they are stored as their code point.
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("CharMap length does not fit in a i32"))?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() * 2 + 1)?;
+        tuple.serialize_element(&len)?;
+        for (key, value) in &self.0 {
+            tuple.serialize_element(&u32::from(*key))?;
+            tuple.serialize_element(value)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for Difficulty {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        match self {
+            Difficulty::Expert(expert) => serializer.serialize_bool(*expert),
+            Difficulty::GameMode(mode) => serializer.serialize_i32(*mode),
+        }
+    }
+}
+
+/// A signed LEB128 integer, serialized as the sequence of its encoded bytes.
+struct Sleb128 (i64);
+
+impl serde::ser::Serialize for Sleb128 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut bytes: Vec<u8> = vec![];
+        leb128::write::signed(&mut bytes, self.0).map_err(|_err| serde::ser::Error::custom("Could not encode SLEB128"))?;
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for byte in &bytes {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for DeltaPosList {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("DeltaPosList length does not fit in a i32"))?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() * 2 + 1)?;
+        tuple.serialize_element(&len)?;
+        if let Some(((first_x, first_y), _rest)) = self.0.split_first() {
+            tuple.serialize_element(first_x)?;
+            tuple.serialize_element(first_y)?;
+        }
+        for window in self.0.windows(2) {
+            let ((previous_x, previous_y), (x, y)) = (window[0], window[1]);
+            tuple.serialize_element(&Sleb128(i64::from(x) - i64::from(previous_x)))?;
+            tuple.serialize_element(&Sleb128(i64::from(y) - i64::from(previous_y)))?;
+        }
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for ExtraHeader {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The bytes are written back verbatim, without any length prefix.
+        let mut tuple = serializer.serialize_tuple(self.bytes.len())?;
+        for byte in &self.bytes {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for NamedKillCounts {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let len = i32::try_from(self.0.len()).map_err(|_err| serde::ser::Error::custom("NamedKillCounts length does not fit in a i32"))?;
+        let mut tuple = serializer.serialize_tuple(self.0.len() * 2 + 1)?;
+        tuple.serialize_element(&len)?;
+        for (name, count) in &self.0 {
+            tuple.serialize_element(name)?;
+            tuple.serialize_element(count)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for PaintColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        if self.0 > PaintColor::MAX {
+            return Err(serde::ser::Error::custom("PaintColor id is out of range"));
+        }
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl serde::ser::Serialize for Guid {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        let mut tuple = serializer.serialize_tuple(self.0.len())?;
+        for byte in &self.0 {
+            tuple.serialize_element(byte)?;
+        };
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for EventTimers {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // Only the groups present in the file are written back, and later groups can't be present without the earlier ones.
+        if self.lantern_night.is_some() && self.sandstorm.is_none() {
+            return Err(serde::ser::Error::custom("EventTimers has a Lantern Night state without a sandstorm state"));
+        }
+        let len = self.sandstorm.iter().count() + self.lantern_night.iter().count();
+        let mut tuple = serializer.serialize_tuple(len)?;
+        if let Some(sandstorm) = &self.sandstorm {
+            tuple.serialize_element(sandstorm)?;
+        }
+        if let Some(lantern_night) = &self.lantern_night {
+            tuple.serialize_element(lantern_night)?;
+        }
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for MapTileColor {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // The light level is only stored if it is not full.
+        let has_light = self.light != u8::MAX;
+        let header = match has_light {
+            true => crate::map::MAP_TILE_HAS_LIGHT,
+            false => 0,
+        };
+        let mut tuple = serializer.serialize_tuple(if has_light { 3 } else { 2 })?;
+        tuple.serialize_element(&header)?;
+        tuple.serialize_element(&self.color)?;
+        if has_light {
+            tuple.serialize_element(&self.light)?;
+        }
+        tuple.end()
+    }
+}
+
+impl serde::ser::Serialize for ShimmerData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::ser::Serializer {
+        // Nothing is written if the fields were missing from the file.
+        let mut tuple = serializer.serialize_tuple(self.0.iter().count())?;
+        if let Some(position) = &self.0 {
+            tuple.serialize_element(position)?;
+        }
+        tuple.end()
+    }
+}
+
+impl_serialize_forward!(
+    BossFlags,
+    Leb128U64,
+    VersionedU8,
+    BoolGrid2D,
+    BlobList,
+    OreTiers,
+    PartyData,
+    MoonData,
+    InvasionData,
+    GenInfo,
+    Tlv,
+    std::ops::Range<i32>,
+    RangeI32,
+    TileShape,
+    Color,
+    OptionalColor,
+    TownNpc,
+    CreativePower,
+    CreativePowers,
+    TimeState,
+    NullableStringI32,
+    MapHeader,
+    LogicSensor,
+    WeightedPlate,
+    WorldNames,
+    VecI32F32,
+    Rect,
+    Backgrounds,
+    DateTime,
+    CharMap,
+    Vector2,
+    Difficulty,
+    DeltaPosList,
+    LayerDepths,
+    ExtraHeader,
+    NamedKillCounts,
+    WorldFooter,
+    PaintColor,
+    ItemStack,
+    ItemFrame,
+    WeaponRack,
+    Guid,
+    Sandstorm,
+    LanternNight,
+    EventTimers,
+    MapTileColor,
+    ShimmerPosition,
+    ShimmerData,
+);
+
+impl_serialize_forward!(<const N: usize> ShopInventory<N>);
+impl_serialize_forward!(<const N: usize, const COUNTED_SINCE: i32> VersionedArray<N, COUNTED_SINCE>);
+impl_serialize_forward!(<const N: usize> BitmaskU64<N>);
+impl_serialize_forward!(<T> OptionBool<T>);
+impl_serialize_forward!(<T> TrailingOption<T>);
+impl_serialize_forward!(<const SCALE: i64> FixedPoint<SCALE>);
+impl_serialize_forward!(<const N: usize> TileCounts<N>);
+impl_serialize_forward!(<const N: usize> BoolArray<N>);
+impl_serialize_forward!(<const SCALE: i64> DeltaF32Series<SCALE>);
+impl_serialize_forward!(<const BITS: u8> PackedNBit<BITS>);
@@ -1,21 +1,126 @@
+use crate::MapTile;
+
 /// Custom serializer trait with support for the weird Terraria array serialization.
 pub trait Serializer : serde::ser::Serializer {
     fn serialize_vec_i16flags(self, len: i16) -> Result<Self::SerializeSeq, Self::Error>;
     fn serialize_vec_uleb128(self, len: usize) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_vec_sleb128(self, len: i64) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_map_uleb128(self, len: usize) -> Result<Self::SerializeMap, Self::Error>;
     fn serialize_vec_i16(self, len: i16) -> Result<Self::SerializeSeq, Self::Error>;
     fn serialize_vec_i32(self, len: i32) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_vec_u8(self, len: u8) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_vec_u32(self, len: u32) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_vec_i64(self, len: i64) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_rle(self, len: i32) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_enum_u8(self, discriminant: u8) -> Result<Self::SerializeSeq, Self::Error>;
+    fn serialize_enum_i32(self, discriminant: i32) -> Result<Self::SerializeSeq, Self::Error>;
 }
 
 
 /// `Write`-based serializer for Terraria world files.
+///
+/// Every primitive is written to the `writer` on its own, so unbuffered writers such as [std::fs::File] should be wrapped in a [std::io::BufWriter]; call [WriteSerializer::finish] to flush it once done.
 pub struct WriteSerializer<W> where W: std::io::Write {
     pub(crate) writer: W,
+
+    /// The amount of bytes written to the `writer` so far.
+    pub(crate) bytes_written: u64,
 }
 
 impl<W> WriteSerializer<W> where W: std::io::Write {
+    /// Create a new serializer writing to the given `writer`.
+    pub fn new(writer: W) -> Self {
+        Self { writer, bytes_written: 0 }
+    }
+
+    /// The amount of bytes written so far, which is the offset of the next value if writing started at the beginning of the file.
+    pub fn bytes_written(&self) -> u64 {
+        self.bytes_written
+    }
+
+    /// Get back the wrapped writer, without flushing it.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Flush the wrapped writer, and get it back.
+    pub fn finish(mut self) -> crate::Result<W> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Write all of `buf` to the `writer`, keeping track of the amount of bytes written.
+    fn write_all(&mut self, buf: &[u8]) -> crate::Result<()> {
+        self.writer.write_all(buf)?;
+        self.bytes_written += buf.len() as u64;
+        Ok(())
+    }
+
     /// Write a ULEB128 value.
     pub fn write_uleb128<T: Into<u64>>(&mut self, val: T) -> crate::Result<()> {
-        leb128::write::unsigned(&mut self.writer, val.into()).map_err(|_err| crate::Error::IO)?;
+        let size = leb128::write::unsigned(&mut self.writer, val.into())?;
+        self.bytes_written += size as u64;
+        Ok(())
+    }
+
+    /// Write the index of the variant of the `enum` called `name`, as an [i32] if the `enum` is named [crate::de::ENUM_I32], or as an [u8] otherwise.
+    fn serialize_discriminant(&mut self, name: &'static str, variant_index: u32) -> crate::Result<&mut Self> {
+        match name {
+            crate::de::ENUM_I32 => {
+                let discriminant = i32::try_from(variant_index).map_err(|_err| crate::Error::Overflow)?;
+                Serializer::serialize_enum_i32(self, discriminant)
+            },
+            _ => {
+                let discriminant = u8::try_from(variant_index).map_err(|_err| crate::Error::Overflow)?;
+                Serializer::serialize_enum_u8(self, discriminant)
+            },
+        }
+    }
+
+    /// Write a SLEB128 value.
+    pub fn write_sleb128<T: Into<i64>>(&mut self, val: T) -> crate::Result<()> {
+        let size = leb128::write::signed(&mut self.writer, val.into())?;
+        self.bytes_written += size as u64;
+        Ok(())
+    }
+
+    /// Write the given [MapTile]s, run-length encoding consecutive equal tiles.
+    pub fn serialize_map_tiles(&mut self, tiles: &[MapTile]) -> crate::Result<()> {
+        let mut index = 0;
+        while index < tiles.len() {
+            let tile = tiles[index];
+            let run = tiles[index..].iter().take(usize::from(u16::MAX) + 1).take_while(|other| **other == tile).count();
+            let repeat = run - 1;
+
+            let mut header = 0;
+            if tile.tile_type > u16::from(u8::MAX) {
+                header |= crate::map::MAP_TILE_WIDE_TYPE;
+            }
+            if tile.light != u8::MAX {
+                header |= crate::map::MAP_TILE_HAS_LIGHT;
+            }
+            header |= match repeat {
+                0 => 0,
+                1..=0xFF => 1,
+                _ => 2,
+            } << crate::map::MAP_TILE_REPEAT_SHIFT;
+
+            self.write_all(&[header])?;
+            match tile.tile_type > u16::from(u8::MAX) {
+                true => self.write_all(&tile.tile_type.to_le_bytes()),
+                false => self.write_all(&[tile.tile_type as u8]),
+            }?;
+            if tile.light != u8::MAX {
+                self.write_all(&[tile.light])?;
+            }
+            match repeat {
+                0 => Ok(()),
+                1..=0xFF => self.write_all(&[repeat as u8]),
+                _ => self.write_all(&(repeat as u16).to_le_bytes()),
+            }?;
+
+            index += run;
+        }
         Ok(())
     }
 }
@@ -59,105 +164,108 @@ impl<W> serde::ser::Serializer for &mut WriteSerializer<W> where W: std::io::Wri
 
     fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
         // `i8`s are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
         // `i16`s ("Int16") are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
         // `i32`s ("Int32") are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
         // `i64`s are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
         // `u8`s ("Byte") are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
         // `u16`s are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
         // `u32`s are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
         // `u64`s are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok, Self::Error> {
         // `f32`s ("Single") are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
     fn serialize_f64(self, v: f64) -> Result<Self::Ok, Self::Error> {
         // `f64`s ("Double") are stored in little-endian byte order.
-        self.writer.write_all(&v.to_le_bytes()).map_err(|_err| crate::Error::IO)
+        self.write_all(&v.to_le_bytes())
     }
 
-    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
-        // `char`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn serialize_char(self, v: char) -> Result<Self::Ok, Self::Error> {
+        // `char`s are stored as strings containing only them.
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
     }
 
     fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
         // `str`s ("String") are stored as sequences of bytes.
         let size = v.len() as u64;
         self.write_uleb128(size)?;
-        self.writer.write(v.as_bytes()).map_err(|_err| crate::Error::IO)?;
+        self.write_all(v.as_bytes())?;
         Ok(())
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        // Terraria has no support for terminated byte-strings.
-        Err(crate::Error::Unsupported)
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        // Byte slices are written as they are, without any length prefix: their length has to be known by the reader.
+        self.write_all(v)?;
+        Ok(())
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
         // `None`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_none"))
     }
 
-    fn serialize_some<T: ?Sized>(self, _value: &T) -> Result<Self::Ok, Self::Error> where T: serde::ser::Serialize {
+    fn serialize_some<T>(self, _value: &T) -> Result<Self::Ok, Self::Error> where T: ?Sized + serde::ser::Serialize {
         // `Some`s don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_some"))
     }
 
     fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
         // Units `()` don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_unit"))
     }
 
     fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
         // Named units can't be serialized in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_unit_struct"))
     }
 
-    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> {
-        // Unit variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn serialize_unit_variant(self, name: &'static str, variant_index: u32, _variant: &'static str) -> Result<Self::Ok, Self::Error> {
+        // Variants are stored as their index, followed by their fields.
+        self.serialize_discriminant(name, variant_index)?;
+        Ok(())
     }
 
-    fn serialize_newtype_struct<T: ?Sized>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where T: serde::ser::Serialize {
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where T: ?Sized + serde::ser::Serialize {
         // `struct`s are handled by serializing their fields in order.
         value.serialize(self)
     }
 
-    fn serialize_newtype_variant<T: ?Sized>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where T: serde::ser::Serialize {
-        // Generic `struct`s are handled by serializing their fields in order.
-        value.serialize(self)
+    fn serialize_newtype_variant<T>(self, name: &'static str, variant_index: u32, _variant: &'static str, value: &T) -> Result<Self::Ok, Self::Error> where T: ?Sized + serde::ser::Serialize {
+        // Variants are stored as their index, followed by their fields.
+        value.serialize(self.serialize_discriminant(name, variant_index)?)
     }
 
     fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
@@ -166,14 +274,14 @@ impl<W> serde::ser::Serializer for &mut WriteSerializer<W> where W: std::io::Wri
         match len {
             Some(len) => {
                 let len = u32::try_from(len).map_err(|_err| crate::Error::Overflow)?;
-                self.writer.write(&len.to_le_bytes()).map_err(|_err| crate::Error::IO)?;
+                self.write_all(&len.to_le_bytes())?;
                 Ok(self)
             },
             // If the length of a sequence is not defined, it cannot be represented in a Terraria save file.
-            None => Err(crate::Error::Unsupported)?,
+            None => Err(crate::Error::Unsupported("serialize_seq"))?,
         }
         */
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_seq"))
     }
 
     fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
@@ -186,14 +294,14 @@ impl<W> serde::ser::Serializer for &mut WriteSerializer<W> where W: std::io::Wri
         self.serialize_tuple(len)
     }
 
-    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
-        // Tuple variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn serialize_tuple_variant(self, name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, Self::Error> {
+        // Variants are stored as their index, followed by their fields.
+        self.serialize_discriminant(name, variant_index)
     }
 
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
         // Maps don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Err(crate::Error::Unsupported("serialize_map"))
     }
 
     fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct, Self::Error> {
@@ -201,30 +309,70 @@ impl<W> serde::ser::Serializer for &mut WriteSerializer<W> where W: std::io::Wri
         self.serialize_tuple(len)
     }
 
-    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
-        // `struct` variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    fn serialize_struct_variant(self, name: &'static str, variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, Self::Error> {
+        // Variants are stored as their index, followed by their fields.
+        self.serialize_discriminant(name, variant_index)
     }
 }
 
 impl<W> Serializer for &mut WriteSerializer<W> where W: std::io::Write {
     fn serialize_vec_i16flags(self, len: i16) -> Result<Self::SerializeSeq, Self::Error> {
-        self.writer.write(&len.to_le_bytes()).map_err(|_err| crate::Error::IO)?;
+        self.write_all(&len.to_le_bytes())?;
         Ok(self)
     }
 
     fn serialize_vec_uleb128(self, len: usize) -> Result<Self::SerializeSeq, Self::Error> {
-        self.writer.write(&len.to_le_bytes()).map_err(|_err| crate::Error::IO)?;
+        self.write_uleb128(len as u64)?;
+        Ok(self)
+    }
+
+    fn serialize_vec_sleb128(self, len: i64) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_sleb128(len)?;
+        Ok(self)
+    }
+
+    fn serialize_map_uleb128(self, len: usize) -> Result<Self::SerializeMap, Self::Error> {
+        self.write_uleb128(len as u64)?;
         Ok(self)
     }
 
     fn serialize_vec_i16(self, len: i16) -> Result<Self::SerializeSeq, Self::Error> {
-        self.writer.write(&len.to_le_bytes()).map_err(|_err| crate::Error::IO)?;
+        self.write_all(&len.to_le_bytes())?;
         Ok(self)
     }
 
     fn serialize_vec_i32(self, len: i32) -> Result<Self::SerializeSeq, Self::Error> {
-        self.writer.write(&len.to_le_bytes()).map_err(|_err| crate::Error::IO)?;
+        self.write_all(&len.to_le_bytes())?;
+        Ok(self)
+    }
+
+    fn serialize_vec_u8(self, len: u8) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&[len])?;
+        Ok(self)
+    }
+
+    fn serialize_vec_u32(self, len: u32) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&len.to_le_bytes())?;
+        Ok(self)
+    }
+
+    fn serialize_vec_i64(self, len: i64) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&len.to_le_bytes())?;
+        Ok(self)
+    }
+
+    fn serialize_rle(self, len: i32) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&len.to_le_bytes())?;
+        Ok(self)
+    }
+
+    fn serialize_enum_u8(self, discriminant: u8) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&[discriminant])?;
+        Ok(self)
+    }
+
+    fn serialize_enum_i32(self, discriminant: i32) -> Result<Self::SerializeSeq, Self::Error> {
+        self.write_all(&discriminant.to_le_bytes())?;
         Ok(self)
     }
 }
@@ -237,7 +385,7 @@ impl<W> serde::ser::SerializeSeq for &mut WriteSerializer<W> where W: std::io::W
     // The result of a failed serialization.
     type Error = crate::Error;
 
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
         // Sequence elements are stored like regular values.
         // I'm not sure why this is a double pointer?
         value.serialize(&mut **self)
@@ -258,7 +406,7 @@ impl<W> serde::ser::SerializeTuple for &mut WriteSerializer<W> where W: std::io:
     type Error = crate::Error;
 
     // Tuple elements are stored like regular values.
-    fn serialize_element<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
         // I'm not sure why this is a double pointer?
         value.serialize(&mut **self)
     }
@@ -278,7 +426,7 @@ impl<W> serde::ser::SerializeTupleStruct for &mut WriteSerializer<W> where W: st
     type Error = crate::Error;
 
     // Tuple `struct`s are stored exactly in the same way as tuples.
-    fn serialize_field<T: ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
         serde::ser::SerializeTuple::serialize_element(self, value)
     }
 
@@ -296,14 +444,14 @@ impl<W> serde::ser::SerializeTupleVariant for &mut WriteSerializer<W> where W: s
     // The result of a failed serialization.
     type Error = crate::Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
-        // Tuple variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    // Tuple variant fields are stored like regular values, after the discriminant.
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
+        value.serialize(&mut **self)
     }
 
+    // Tuple variants don't have an end marker in Terraria save files.
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        // Tuple variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Ok(())
     }
 }
 
@@ -315,19 +463,19 @@ impl<W> serde::ser::SerializeMap for &mut WriteSerializer<W> where W: std::io::W
     // The result of a failed serialization.
     type Error = crate::Error;
 
-    fn serialize_key<T: ?Sized>(&mut self, _key: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
-        // Maps don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    // Keys are stored like regular values, before their value.
+    fn serialize_key<T>(&mut self, key: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
+        key.serialize(&mut **self)
     }
 
-    fn serialize_value<T: ?Sized>(&mut self, _value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
-        // Maps don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    // Values are stored like regular values, after their key.
+    fn serialize_value<T>(&mut self, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
+        value.serialize(&mut **self)
     }
 
+    // Maps are only created through [Serializer::serialize_map_uleb128], and don't have an end marker.
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        // Maps don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Ok(())
     }
 }
 
@@ -340,7 +488,7 @@ impl<W> serde::ser::SerializeStruct for &mut WriteSerializer<W> where W: std::io
     type Error = crate::Error;
 
     // `struct`s are handled like tuples; keys are ignored.
-    fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
         serde::ser::SerializeTuple::serialize_element(self, value)
     }
 
@@ -358,13 +506,112 @@ impl<W> serde::ser::SerializeStructVariant for &mut WriteSerializer<W> where W:
     // The result of a failed serialization.
     type Error = crate::Error;
 
-    fn serialize_field<T: ?Sized>(&mut self, _key: &'static str, _value: &T) -> Result<(), Self::Error> where T: serde::ser::Serialize {
-        // `struct` variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+    // `struct` variant fields are stored like regular values, after the discriminant; keys are ignored.
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error> where T: ?Sized + serde::ser::Serialize {
+        value.serialize(&mut **self)
     }
 
+    // `struct` variants don't have an end marker in Terraria save files.
     fn end(self) -> Result<Self::Ok, Self::Error> {
-        // `struct` variants don't exist in Terraria save files.
-        Err(crate::Error::Unsupported)
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_names_method() {
+        let mut ser = WriteSerializer::new(vec![]);
+        let map: std::collections::HashMap<u8, u8> = std::collections::HashMap::new();
+        assert_eq!(serde::ser::Serialize::serialize(&map, &mut ser).err(), Some(crate::Error::Unsupported("serialize_map")));
+        assert_eq!(serde::ser::Serialize::serialize(&None::<u8>, &mut ser).err(), Some(crate::Error::Unsupported("serialize_none")));
+        assert_eq!(serde::ser::Serialize::serialize(&(), &mut ser).err(), Some(crate::Error::Unsupported("serialize_unit")));
+    }
+
+    /// A [std::io::Write]r accepting at most `limit` bytes per write, like a socket under backpressure.
+    struct ShortWriter {
+        bytes: Vec<u8>,
+        limit: usize,
+    }
+
+    impl std::io::Write for ShortWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let size = buf.len().min(self.limit);
+            self.bytes.extend_from_slice(&buf[..size]);
+            Ok(size)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct Prefixed {
+        vec_i32: crate::VecI32<i32>,
+        vec_i16: crate::VecI16<i16>,
+        vec_uleb128: crate::VecULEB128<u8>,
+        text: String,
+    }
+
+    impl_serialize_forward!(Prefixed);
+
+    fn prefixed() -> Prefixed {
+        Prefixed { vec_i32: crate::VecI32(vec![1, 2]), vec_i16: crate::VecI16(vec![3]), vec_uleb128: crate::VecULEB128(vec![4]), text: "text".to_string() }
+    }
+
+    #[test]
+    fn short_writes_are_completed() {
+        let writer = crate::to_writer(ShortWriter { bytes: vec![], limit: 1 }, prefixed()).unwrap();
+        assert_eq!(writer.bytes, crate::to_bytes(prefixed()).unwrap());
+        assert_eq!(writer.bytes.len(), (4 + 2 * 4) + (2 + 2) + (1 + 1) + (1 + 4));
+
+        // The length prefix hooks are used when serializing the vectors directly.
+        let writer = crate::to_writer(ShortWriter { bytes: vec![], limit: 3 }, crate::VecI32(vec![1_i32, 2])).unwrap();
+        assert_eq!(writer.bytes, [2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0]);
+        let writer = crate::to_writer(ShortWriter { bytes: vec![], limit: 1 }, crate::VecULEB128(vec![0_u8; 200])).unwrap();
+        assert_eq!(writer.bytes[..2], [0xC8, 0x01]);
+        assert_eq!(writer.bytes.len(), 202);
+    }
+
+    #[test]
+    fn zero_writes_fail() {
+        let result = crate::to_writer(ShortWriter { bytes: vec![], limit: 0 }, crate::VecI32(vec![1_i32]));
+        assert_eq!(result.err(), Some(crate::Error::IO(std::io::Error::from(std::io::ErrorKind::WriteZero).into())));
+    }
+
+    /// A writer failing once it received `capacity` bytes, accepting only part of a buffer which doesn't fit.
+    struct FullWriter {
+        bytes: Vec<u8>,
+        capacity: usize,
+    }
+
+    impl std::io::Write for FullWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let size = buf.len().min(self.capacity - self.bytes.len());
+            if size == 0 && !buf.is_empty() {
+                return Err(std::io::Error::new(std::io::ErrorKind::Other, "device full"))
+            }
+            self.bytes.extend_from_slice(&buf[..size]);
+            Ok(size)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn truncated_strings_fail() {
+        let mut ser = crate::WriteSerializer::new(FullWriter { bytes: vec![], capacity: 4 });
+        let result = serde::Serialize::serialize("Terraria", &mut ser);
+        assert_eq!(result.err(), Some(crate::Error::IO(std::io::Error::from(std::io::ErrorKind::Other).into())));
+        assert_eq!(ser.into_inner().bytes, [8, b'T', b'e', b'r']);
+
+        let mut ser = crate::WriteSerializer::new(FullWriter { bytes: vec![], capacity: 9 });
+        serde::Serialize::serialize("Terraria", &mut ser).unwrap();
+        assert_eq!(ser.into_inner().bytes, b"\x08Terraria");
     }
 }
@@ -0,0 +1,724 @@
+/// A [u64] serialized as a variable-length ULEB128 integer.
+pub struct Leb128U64 (pub u64);
+
+/// A [u8] appended to the end of the file by later versions, which defaults to `0` if the file ends before it.
+pub struct VersionedU8 (pub u8);
+
+/// A `width`×`height` grid of `bool`s, serialized as its dimensions followed by its row-major bits, packed 8 per byte.
+pub struct BoolGrid2D {
+    /// The amount of columns of the grid.
+    pub width: i32,
+
+    /// The amount of rows of the grid.
+    pub height: i32,
+
+    /// The cells of the grid, in row-major order.
+    pub bits: Vec<bool>,
+}
+
+impl BoolGrid2D {
+    /// Get the cell at the given coordinates, if it is inside the grid.
+    pub fn get(&self, x: i32, y: i32) -> Option<bool> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None
+        }
+        self.bits.get((y as usize) * (self.width as usize) + (x as usize)).copied()
+    }
+}
+
+/// A [i32]-sized list of byte blobs, each serialized as a ULEB128-sized sequence of bytes.
+pub struct BlobList (pub Vec<Vec<u8>>);
+
+/// A tag-length-value record, serialized as its [u16] tag, the [u32] size of its value, and the bytes of its value.
+pub struct Tlv {
+    /// The tag identifying the kind of the record.
+    pub tag: u16,
+
+    /// The contents of the record.
+    pub value: Vec<u8>,
+}
+
+/// A range of [i32]s, serialized as its `start` followed by its `end`.
+///
+/// Unlike a plain [std::ops::Range], it can be checked while being read: see [crate::ReadDeserializer::reject_inverted_ranges].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeI32 (pub std::ops::Range<i32>);
+
+/// A list of [i32]s stored with an [i32] count from version `COUNTED_SINCE` onwards, and as exactly `N` values before.
+pub struct VersionedArray<const N: usize, const COUNTED_SINCE: i32> {
+    /// Whether the list is stored in the counted form.
+    pub counted: bool,
+
+    /// The values of the list.
+    pub values: Vec<i32>,
+}
+
+/// `N` `bool`s, serialized as the bits of a single [u64], least significant first.
+///
+/// `N` must be at most 64, which is checked at compile time; the bits of the [u64] past the first `N` are written as `0` and ignored when read.
+///
+/// ```compile_fail
+/// serde_altar::to_bytes(serde_altar::BitmaskU64 { bits: [false; 65] }).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BitmaskU64<const N: usize> {
+    /// The bits of the mask.
+    pub bits: [bool; N],
+}
+
+impl<const N: usize> BitmaskU64<N> {
+    /// The amount of unused bits of the [u64], whose evaluation fails to compile if `N` is greater than 64.
+    ///
+    /// `panic!` can't be used in constants on the minimum supported Rust version, but an overflow stops compilation just the same.
+    pub(crate) const UNUSED_BITS: usize = 64 - N;
+}
+
+/// A color, serialized as its red, green and blue [u8] components.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Color {
+    /// The red component.
+    pub r: u8,
+
+    /// The green component.
+    pub g: u8,
+
+    /// The blue component.
+    pub b: u8,
+}
+
+/// A [Color] which may be missing, serialized as a `bool` signaling its presence followed by the color, if present.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OptionalColor (pub Option<Color>);
+
+/// A fractional number, serialized as an [i32] holding its value multiplied by `SCALE` and rounded.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedPoint<const SCALE: i64> (pub f64);
+
+/// A string which may be null, serialized as its [i32] byte length followed by its bytes, where a length of `-1` means null.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NullableStringI32 (pub Option<String>);
+
+/// A series of [f32]s, serialized as its [i32] length, its first value, and the difference of each value from the previous one as an [i16] multiple of `1 / SCALE`.
+///
+/// Values are reconstructed within half of `1 / SCALE` from the original ones.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeltaF32Series<const SCALE: i64> (pub Vec<f32>);
+
+/// A list of `BITS`-wide values, serialized as its [i32] length followed by the values packed `8 / BITS` per byte, least significant first.
+///
+/// `BITS` must be a power of two no greater than `8`, such as `4` for liquid types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PackedNBit<const BITS: u8> (pub Vec<u8>);
+
+/// A value which may be missing, serialized as the value followed by a `bool` signaling whether it should be kept.
+///
+/// The value is always stored, even when missing, so it is kept around to be written back as it was.
+///
+/// To avoid fully decoding values which turn out to be missing, see [crate::SliceDeserializer::rollback].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrailingOption<T> {
+    /// The stored value, which should be ignored if not `present`.
+    pub value: T,
+
+    /// Whether the value should be kept.
+    pub present: bool,
+}
+
+impl<T> TrailingOption<T> {
+    /// The value, if it is present.
+    pub fn get(&self) -> Option<&T> {
+        match self.present {
+            true => Some(&self.value),
+            false => None,
+        }
+    }
+
+    /// Take the value, if it is present.
+    pub fn into_option(self) -> Option<T> {
+        match self.present {
+            true => Some(self.value),
+            false => None,
+        }
+    }
+}
+
+/// A rectangle of tiles, serialized as its left, right, top and bottom edges as [i32]s.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Rect {
+    /// The x coordinate of the left edge.
+    pub left: i32,
+
+    /// The x coordinate of the right edge.
+    pub right: i32,
+
+    /// The y coordinate of the top edge.
+    pub top: i32,
+
+    /// The y coordinate of the bottom edge.
+    pub bottom: i32,
+}
+
+impl Rect {
+    /// The horizontal size of the rectangle.
+    pub fn width(&self) -> i32 {
+        self.right - self.left
+    }
+
+    /// The vertical size of the rectangle.
+    pub fn height(&self) -> i32 {
+        self.bottom - self.top
+    }
+
+    /// Whether the given coordinates are inside the rectangle, including its left and top edges but not its right and bottom ones.
+    pub fn contains(&self, x: i32, y: i32) -> bool {
+        x >= self.left && x < self.right && y >= self.top && y < self.bottom
+    }
+}
+
+/// The kind of a [DateTime], stored in its two most significant bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DateTimeKind {
+    /// The time zone of the timestamp is not known.
+    Unspecified,
+
+    /// The timestamp is in UTC.
+    Utc,
+
+    /// The timestamp was in local time, and has been converted to UTC when it was stored.
+    Local,
+}
+
+/// Bits of a [DateTime] storing its [DateTimeKind].
+pub(crate) const DATE_TIME_KIND_MASK: u64 = 0xC000_0000_0000_0000;
+
+/// Bits of a [DateTime] storing its ticks.
+pub(crate) const DATE_TIME_TICKS_MASK: u64 = 0x3FFF_FFFF_FFFF_FFFF;
+
+/// The amount of ticks between the .NET epoch (January 1st of year 1) and the Unix epoch.
+pub(crate) const DATE_TIME_UNIX_EPOCH_TICKS: u64 = 621_355_968_000_000_000;
+
+/// The amount of nanoseconds in a tick.
+pub(crate) const DATE_TIME_TICK_NANOS: u64 = 100;
+
+/// A .NET `DateTime`, serialized as the [i64] returned by `DateTime.ToBinary()`.
+///
+/// The two most significant bits store the [DateTimeKind], and the others the amount of 100 nanoseconds ticks since January 1st of year 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct DateTime (pub i64);
+
+impl DateTime {
+    /// The kind of the timestamp.
+    pub fn kind(&self) -> DateTimeKind {
+        match (self.0 as u64 & DATE_TIME_KIND_MASK) >> 62 {
+            0 => DateTimeKind::Unspecified,
+            1 => DateTimeKind::Utc,
+            // Both values mean local time; 3 marks timestamps falling in an ambiguous daylight saving hour.
+            _ => DateTimeKind::Local,
+        }
+    }
+
+    /// The amount of 100 nanoseconds ticks since January 1st of year 1.
+    pub fn ticks(&self) -> u64 {
+        self.0 as u64 & DATE_TIME_TICKS_MASK
+    }
+
+    /// Create an UTC timestamp from the given [std::time::SystemTime].
+    ///
+    /// Precision below a tick is lost.
+    pub fn from_system_time(time: std::time::SystemTime) -> crate::Result<Self> {
+        let ticks = match time.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => u64::try_from(after.as_nanos() / u128::from(DATE_TIME_TICK_NANOS)).ok()
+                .and_then(|ticks| DATE_TIME_UNIX_EPOCH_TICKS.checked_add(ticks)),
+            Err(err) => u64::try_from(err.duration().as_nanos() / u128::from(DATE_TIME_TICK_NANOS)).ok()
+                .and_then(|ticks| DATE_TIME_UNIX_EPOCH_TICKS.checked_sub(ticks)),
+        };
+        match ticks {
+            Some(ticks) if ticks <= DATE_TIME_TICKS_MASK => Ok(DateTime((ticks | (1 << 62)) as i64)),
+            _ => Err(crate::Error::Overflow),
+        }
+    }
+
+    /// Convert the timestamp to a [std::time::SystemTime], assuming it is in UTC if its kind is [DateTimeKind::Unspecified].
+    ///
+    /// Returns [None] if the platform can't represent the timestamp.
+    pub fn to_system_time(&self) -> Option<std::time::SystemTime> {
+        let ticks = self.ticks();
+        let ticks_per_second = 1_000_000_000 / DATE_TIME_TICK_NANOS;
+        let duration = |ticks: u64| std::time::Duration::new(ticks / ticks_per_second, ((ticks % ticks_per_second) * DATE_TIME_TICK_NANOS) as u32);
+        match ticks.checked_sub(DATE_TIME_UNIX_EPOCH_TICKS) {
+            Some(after) => std::time::UNIX_EPOCH.checked_add(duration(after)),
+            None => std::time::UNIX_EPOCH.checked_sub(duration(DATE_TIME_UNIX_EPOCH_TICKS - ticks)),
+        }
+    }
+}
+
+/// A lookup table from `char`s to strings, serialized as its [i32] length followed by each key as an [u32] code point and its string.
+///
+/// Keys are stored in ascending order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CharMap (pub std::collections::BTreeMap<char, String>);
+
+/// A position or a velocity, serialized as its x and y [f32] coordinates.
+#[derive(Clone, Copy, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Vector2 {
+    /// The horizontal coordinate.
+    pub x: f32,
+
+    /// The vertical coordinate.
+    pub y: f32,
+}
+
+impl std::ops::Add for Vector2 {
+    type Output = Vector2;
+
+    fn add(self, other: Vector2) -> Vector2 {
+        Vector2 { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+impl std::ops::Sub for Vector2 {
+    type Output = Vector2;
+
+    fn sub(self, other: Vector2) -> Vector2 {
+        Vector2 { x: self.x - other.x, y: self.y - other.y }
+    }
+}
+
+/// A list of tile positions, serialized as its [i32] length, the first position as two [i32]s, and the difference of each position from the previous one as two signed LEB128 integers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeltaPosList (pub Vec<(i32, i32)>);
+
+/// A value which may be missing, serialized as a `bool` signaling its presence followed by the value, if present.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OptionBool<T> (pub Option<T>);
+
+/// Header fields unknown to this library, kept as opaque bytes so that they can be written back unchanged.
+///
+/// Newer versions of the game may add fields to a header; their size has to be determined from the file version by the caller.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExtraHeader {
+    /// The raw bytes of the unknown fields.
+    pub bytes: Vec<u8>,
+}
+
+/// A fixed amount of `bool`s, each stored as its own byte instead of being packed like in [crate::VecI16Flags].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoolArray<const N: usize> (pub [bool; N]);
+
+/// A globally unique identifier, stored as its 16 raw bytes.
+///
+/// The bytes are kept in file order, which is the one of .NET's `Guid.ToByteArray`: the first three groups are little-endian.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Guid (pub [u8; 16]);
+
+impl std::fmt::Display for Guid {
+    /// Format the identifier in its canonical `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx` form, as .NET would.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let b = &self.0;
+        write!(
+            f,
+            "{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}{:02x}{:02x}{:02x}{:02x}",
+            b[3], b[2], b[1], b[0], b[5], b[4], b[7], b[6], b[8], b[9], b[10], b[11], b[12], b[13], b[14], b[15],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leb128_u64_roundtrip() {
+        for (value, size) in [(0, 1), (300, 2), (u64::MAX, 10)] {
+            let bytes = crate::to_bytes(Leb128U64(value)).unwrap();
+            assert_eq!(bytes.len(), size);
+            let decoded: Leb128U64 = crate::from_slice(&bytes).unwrap();
+            assert_eq!(decoded.0, value);
+        }
+    }
+
+    #[test]
+    fn leb128_u64_overflow() {
+        // The tenth group can only hold the most significant bit of an u64.
+        let bytes = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F];
+        let result: crate::Result<Leb128U64> = crate::from_slice(&bytes);
+        assert_eq!(result.err(), Some(crate::Error::Overflow));
+    }
+
+    #[derive(serde::Deserialize)]
+    struct TrailingHeader {
+        revision: i32,
+        flag: VersionedU8,
+    }
+
+    #[test]
+    fn versioned_u8_present() {
+        let bytes = crate::to_bytes(VersionedU8(7)).unwrap();
+        assert_eq!(bytes, [7]);
+        let header: TrailingHeader = crate::from_slice(&[1, 0, 0, 0, 7]).unwrap();
+        assert_eq!(header.revision, 1);
+        assert_eq!(header.flag.0, 7);
+    }
+
+    #[test]
+    fn versioned_u8_absent() {
+        // Older files end before the flag.
+        let header: TrailingHeader = crate::from_slice(&[1, 0, 0, 0]).unwrap();
+        assert_eq!(header.revision, 1);
+        assert_eq!(header.flag.0, 0);
+    }
+
+    #[test]
+    fn bool_grid_2d_roundtrip() {
+        let bits: Vec<bool> = (0..100).map(|index| index % 7 == 0 || index % 13 == 5).collect();
+        let grid = BoolGrid2D { width: 10, height: 10, bits: bits.clone() };
+        let bytes = crate::to_bytes(grid).unwrap();
+        // 100 bits take 13 bytes, the last one padded.
+        assert_eq!(bytes.len(), 4 + 4 + 13);
+        let decoded: BoolGrid2D = crate::from_slice(&bytes).unwrap();
+        assert_eq!((decoded.width, decoded.height), (10, 10));
+        assert_eq!(decoded.bits, bits);
+        assert_eq!(decoded.get(0, 0), Some(true));
+        assert_eq!(decoded.get(5, 0), Some(true));
+        assert_eq!(decoded.get(1, 0), Some(false));
+        assert_eq!(decoded.get(10, 0), None);
+    }
+
+    #[test]
+    fn bool_grid_2d_mismatched_bits() {
+        let grid = BoolGrid2D { width: 3, height: 3, bits: vec![true; 8] };
+        assert!(crate::to_bytes(grid).is_err());
+    }
+
+    #[test]
+    fn blob_list_roundtrip() {
+        let blobs = vec![vec![1, 2, 3], vec![], vec![0xAB; 200]];
+        let bytes = crate::to_bytes(BlobList(blobs.clone())).unwrap();
+        // The last blob needs a two bytes ULEB128 size.
+        assert_eq!(bytes.len(), 4 + (1 + 3) + 1 + (2 + 200));
+        let decoded: BlobList = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0, blobs);
+    }
+
+    #[test]
+    fn blob_list_truncated() {
+        let mut bytes = crate::to_bytes(BlobList(vec![vec![1, 2, 3]])).unwrap();
+        bytes.pop();
+        let result: crate::Result<BlobList> = crate::from_slice(&bytes);
+        assert_eq!(result.err(), Some(crate::Error::Eof));
+    }
+
+    #[test]
+    fn tlv_roundtrip() {
+        let bytes = crate::to_bytes(Tlv { tag: 0x0102, value: vec![9, 8, 7] }).unwrap();
+        assert_eq!(bytes, [0x02, 0x01, 3, 0, 0, 0, 9, 8, 7]);
+        let decoded: Tlv = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.tag, 0x0102);
+        assert_eq!(decoded.value, [9, 8, 7]);
+    }
+
+    #[test]
+    fn tlv_oversized_length() {
+        let bytes = [0x02, 0x01, 0xFF, 0xFF, 0xFF, 0x7F];
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).max_alloc(1024);
+        let result = <Tlv as serde::Deserialize>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::AllocationLimit { requested: 0x7FFF_FFFF, limit: 1024 }));
+    }
+
+    #[test]
+    fn range_i32_roundtrip() {
+        let bytes = crate::to_bytes(RangeI32(-5..10)).unwrap();
+        assert_eq!(bytes, [0xFB, 0xFF, 0xFF, 0xFF, 10, 0, 0, 0]);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).reject_inverted_ranges(true);
+        let range = <RangeI32 as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert_eq!(range, RangeI32(-5..10));
+    }
+
+    #[test]
+    fn range_i32_inverted() {
+        let bytes = crate::to_bytes(RangeI32(std::ops::Range { start: 10, end: -5 })).unwrap();
+        // Inverted ranges are only rejected if asked to.
+        let range: RangeI32 = crate::from_slice(&bytes).unwrap();
+        assert_eq!(range, RangeI32(std::ops::Range { start: 10, end: -5 }));
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).reject_inverted_ranges(true);
+        let result = <RangeI32 as serde::Deserialize>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::InvertedRange { start: 10, end: -5 }));
+    }
+
+    #[test]
+    fn std_range_is_not_checked() {
+        // Plain ranges go through the regular `struct` path, whatever the type of their bounds.
+        let bytes = crate::to_bytes(std::ops::Range { start: 10_i32, end: -5 }).unwrap();
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).reject_inverted_ranges(true);
+        let range = <std::ops::Range<i32> as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert_eq!(range, std::ops::Range { start: 10, end: -5 });
+        let bytes = [1_u8, 2];
+        let range: std::ops::Range<u8> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(range, 1..2);
+    }
+
+    #[test]
+    fn versioned_array_fixed() {
+        let array = VersionedArray::<3, 200> { counted: false, values: vec![1, 2, 3] };
+        let bytes = crate::to_bytes(array).unwrap();
+        assert_eq!(bytes.len(), 3 * 4);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).with_version(199);
+        let decoded = <VersionedArray<3, 200> as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert!(!decoded.counted);
+        assert_eq!(decoded.values, [1, 2, 3]);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn versioned_array_counted() {
+        let array = VersionedArray::<3, 200> { counted: true, values: vec![4, 5, 6, 7, 8] };
+        let bytes = crate::to_bytes(array).unwrap();
+        assert_eq!(bytes.len(), 4 + 5 * 4);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).with_version(200);
+        let decoded = <VersionedArray<3, 200> as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert!(decoded.counted);
+        assert_eq!(decoded.values, [4, 5, 6, 7, 8]);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn versioned_array_fixed_wrong_length() {
+        let array = VersionedArray::<3, 200> { counted: false, values: vec![1, 2] };
+        assert!(crate::to_bytes(array).is_err());
+    }
+
+    #[test]
+    fn bitmask_u64_roundtrip() {
+        let one = BitmaskU64 { bits: [true] };
+        let bytes = crate::to_bytes(one).unwrap();
+        assert_eq!(bytes, 1_u64.to_le_bytes());
+        assert_eq!(crate::from_slice::<BitmaskU64<1>>(&bytes).unwrap(), one);
+
+        let mut bits = [false; 40];
+        bits[0] = true;
+        bits[39] = true;
+        let forty = BitmaskU64 { bits };
+        let bytes = crate::to_bytes(forty).unwrap();
+        assert_eq!(bytes, (1_u64 | 1 << 39).to_le_bytes());
+        assert_eq!(crate::from_slice::<BitmaskU64<40>>(&bytes).unwrap(), forty);
+
+        let all = BitmaskU64 { bits: [true; 64] };
+        let bytes = crate::to_bytes(all).unwrap();
+        assert_eq!(bytes, u64::MAX.to_le_bytes());
+        assert_eq!(crate::from_slice::<BitmaskU64<64>>(&bytes).unwrap(), all);
+    }
+
+    #[test]
+    fn bitmask_u64_truncates() {
+        let bytes = u64::MAX.to_le_bytes();
+        let decoded: BitmaskU64<3> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.bits, [true; 3]);
+    }
+
+    #[test]
+    fn bitmask_u64_unused_bits() {
+        assert_eq!(BitmaskU64::<1>::UNUSED_BITS, 63);
+        assert_eq!(BitmaskU64::<64>::UNUSED_BITS, 0);
+    }
+
+    #[test]
+    fn optional_color_roundtrip() {
+        let absent = crate::to_bytes(OptionalColor(None)).unwrap();
+        assert_eq!(absent, [0]);
+        assert_eq!(crate::from_slice::<OptionalColor>(&absent).unwrap(), OptionalColor(None));
+
+        let color = OptionalColor(Some(Color { r: 255, g: 128, b: 1 }));
+        let present = crate::to_bytes(color).unwrap();
+        assert_eq!(present, [1, 255, 128, 1]);
+        assert_eq!(crate::from_slice::<OptionalColor>(&present).unwrap(), color);
+    }
+
+    #[test]
+    fn optional_color_truncated() {
+        assert_eq!(crate::from_slice::<OptionalColor>(&[1, 255]).err(), Some(crate::Error::At { position: 2, error: Box::new(crate::Error::Eof) }));
+    }
+
+    #[test]
+    fn fixed_point_roundtrip() {
+        let bytes = crate::to_bytes(FixedPoint::<100>(12.345)).unwrap();
+        assert_eq!(bytes, 1235_i32.to_le_bytes());
+        assert_eq!(crate::from_slice::<FixedPoint<100>>(&bytes).unwrap(), FixedPoint(12.35));
+
+        let bytes = crate::to_bytes(FixedPoint::<100>(-0.004)).unwrap();
+        assert_eq!(bytes, 0_i32.to_le_bytes());
+
+        let bytes = (-250_i32).to_le_bytes();
+        assert_eq!(crate::from_slice::<FixedPoint<100>>(&bytes).unwrap(), FixedPoint(-2.5));
+    }
+
+    #[test]
+    fn fixed_point_out_of_range() {
+        assert!(crate::to_bytes(FixedPoint::<100>(i32::MAX as f64)).is_err());
+    }
+
+    #[test]
+    fn nullable_string_i32_roundtrip() {
+        let null = crate::to_bytes(NullableStringI32(None)).unwrap();
+        assert_eq!(null, (-1_i32).to_le_bytes());
+        assert_eq!(crate::from_slice::<NullableStringI32>(&null).unwrap(), NullableStringI32(None));
+
+        let empty = crate::to_bytes(NullableStringI32(Some(String::new()))).unwrap();
+        assert_eq!(empty, 0_i32.to_le_bytes());
+        assert_eq!(crate::from_slice::<NullableStringI32>(&empty).unwrap(), NullableStringI32(Some(String::new())));
+
+        let normal = crate::to_bytes(NullableStringI32(Some("Guide".to_string()))).unwrap();
+        assert_eq!(normal, [5, 0, 0, 0, b'G', b'u', b'i', b'd', b'e']);
+        assert_eq!(crate::from_slice::<NullableStringI32>(&normal).unwrap(), NullableStringI32(Some("Guide".to_string())));
+    }
+
+    #[test]
+    fn nullable_string_i32_encoding() {
+        let bytes = [2, 0, 0, 0, b'a', 0xE9];
+        assert_eq!(crate::from_slice::<NullableStringI32>(&bytes).err(), Some(crate::Error::At { position: 4, error: Box::new(crate::Error::Utf8 { valid_up_to: 1, sequence: vec![0xE9] }) }));
+
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).string_encoding(crate::StringEncoding::Latin1);
+        let decoded = <NullableStringI32 as serde::Deserialize>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded, NullableStringI32(Some("a\u{E9}".to_string())));
+    }
+
+    #[test]
+    fn nullable_string_i32_negative_length() {
+        let bytes = (-2_i32).to_le_bytes();
+        assert_eq!(crate::from_slice::<NullableStringI32>(&bytes).err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Overflow) }));
+    }
+
+    #[test]
+    fn delta_f32_series_roundtrip() {
+        let values: Vec<f32> = (0..500).map(|index| (index as f32 * 0.1).sin() * 20.0 + 5.0).collect();
+        let bytes = crate::to_bytes(DeltaF32Series::<100>(values.clone())).unwrap();
+        assert_eq!(bytes.len(), 4 + 4 + 499 * 2);
+
+        let decoded: DeltaF32Series<100> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0.len(), values.len());
+        assert_eq!(decoded.0[0], values[0]);
+        for (original, reconstructed) in values.iter().zip(&decoded.0) {
+            assert!((original - reconstructed).abs() <= 0.5 / 100.0 + 1e-4, "{} reconstructed as {}", original, reconstructed);
+        }
+
+        let empty = crate::to_bytes(DeltaF32Series::<100>(vec![])).unwrap();
+        assert_eq!(empty, 0_i32.to_le_bytes());
+        assert_eq!(crate::from_slice::<DeltaF32Series<100>>(&empty).unwrap(), DeltaF32Series(vec![]));
+    }
+
+    #[test]
+    fn delta_f32_series_delta_too_large() {
+        assert!(crate::to_bytes(DeltaF32Series::<100>(vec![0.0, 1000.0])).is_err());
+    }
+
+    #[test]
+    fn packed_n_bit_roundtrip() {
+        let values = vec![0x1, 0xF, 0x0, 0xA, 0x7];
+        let bytes = crate::to_bytes(PackedNBit::<4>(values.clone())).unwrap();
+        assert_eq!(bytes, [5, 0, 0, 0, 0xF1, 0xA0, 0x07]);
+        assert_eq!(crate::from_slice::<PackedNBit<4>>(&bytes).unwrap(), PackedNBit(values));
+
+        let values = vec![1, 0, 1];
+        let bytes = crate::to_bytes(PackedNBit::<1>(values.clone())).unwrap();
+        assert_eq!(bytes, [3, 0, 0, 0, 0b101]);
+        assert_eq!(crate::from_slice::<PackedNBit<1>>(&bytes).unwrap(), PackedNBit(values));
+    }
+
+    #[test]
+    fn packed_n_bit_invalid() {
+        assert!(crate::to_bytes(PackedNBit::<4>(vec![0x10])).is_err());
+        assert!(crate::to_bytes(PackedNBit::<3>(vec![0])).is_err());
+        assert!(crate::from_slice::<PackedNBit<4>>(&[3, 0, 0, 0, 0xFF]).is_err());
+    }
+
+    #[test]
+    fn trailing_option_roundtrip() {
+        let present = TrailingOption { value: 7_i16, present: true };
+        let bytes = crate::to_bytes(present.clone()).unwrap();
+        assert_eq!(bytes, [7, 0, 1]);
+        let decoded: TrailingOption<i16> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, present);
+        assert_eq!(decoded.get(), Some(&7));
+
+        // Missing values are written back exactly as they were read.
+        let bytes = [9, 0, 0];
+        let decoded: TrailingOption<i16> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.get(), None);
+        assert_eq!(crate::to_bytes(decoded.clone()).unwrap(), bytes);
+        assert_eq!(decoded.into_option(), None);
+    }
+
+    #[test]
+    fn char_map_roundtrip() {
+        let mut map = std::collections::BTreeMap::new();
+        map.insert('a', "apple".to_string());
+        map.insert('\u{00E9}', "\u{00E9}clair".to_string());
+        map.insert('\u{1F333}', String::new());
+        let bytes = crate::to_bytes(CharMap(map.clone())).unwrap();
+        let mut expected = 3_i32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&('a' as u32).to_le_bytes());
+        expected.push(5);
+        expected.extend_from_slice(b"apple");
+        expected.extend_from_slice(&0xE9_u32.to_le_bytes());
+        expected.push(7);
+        expected.extend_from_slice("\u{00E9}clair".as_bytes());
+        expected.extend_from_slice(&0x1F333_u32.to_le_bytes());
+        expected.push(0);
+        assert_eq!(bytes, expected);
+        assert_eq!(crate::from_slice::<CharMap>(&bytes).unwrap(), CharMap(map));
+    }
+
+    #[test]
+    fn char_map_invalid_char() {
+        let mut bytes = 1_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&0xD800_u32.to_le_bytes());
+        bytes.push(0);
+        assert!(crate::from_slice::<CharMap>(&bytes).is_err());
+    }
+
+    #[test]
+    fn delta_pos_list_roundtrip() {
+        let positions = vec![(100, 200), (101, 199), (37, 300), (37, 300), (-5, 0)];
+        let bytes = crate::to_bytes(DeltaPosList(positions.clone())).unwrap();
+        let mut expected = 5_i32.to_le_bytes().to_vec();
+        expected.extend_from_slice(&100_i32.to_le_bytes());
+        expected.extend_from_slice(&200_i32.to_le_bytes());
+        // +1, -1 | -64, +101 | 0, 0 | -42, -300
+        expected.extend_from_slice(&[0x01, 0x7F, 0x40, 0xE5, 0x00, 0x00, 0x00, 0x56, 0xD4, 0x7D]);
+        assert_eq!(bytes, expected);
+        assert_eq!(crate::from_slice::<DeltaPosList>(&bytes).unwrap(), DeltaPosList(positions));
+
+        let empty = crate::to_bytes(DeltaPosList(vec![])).unwrap();
+        assert_eq!(empty, 0_i32.to_le_bytes());
+        assert_eq!(crate::from_slice::<DeltaPosList>(&empty).unwrap(), DeltaPosList(vec![]));
+    }
+
+    #[test]
+    fn extra_header_round_trip() {
+        // Five unknown header bytes, followed by a field this library understands.
+        let bytes = [0xDE, 0xAD, 0x00, 0xBE, 0xEF, 42, 0, 0, 0];
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let extra = ExtraHeader::decode(&mut de, 5).unwrap();
+        assert_eq!(extra.bytes, [0xDE, 0xAD, 0x00, 0xBE, 0xEF]);
+        let known: i32 = serde::Deserialize::deserialize(&mut de).unwrap();
+        assert_eq!(known, 42);
+        assert_eq!(de.position(), 9);
+
+        assert_eq!(crate::to_bytes(extra).unwrap(), bytes[..5]);
+        assert!(ExtraHeader::decode(&mut crate::SliceDeserializer::from_slice(&bytes[..4]), 5).is_err());
+    }
+
+    #[test]
+    fn bool_array_round_trip() {
+        let array = BoolArray([true, false, false, true, true]);
+        let bytes = [1, 0, 0, 1, 1];
+        assert_eq!(crate::to_bytes(array).unwrap(), bytes);
+        assert_eq!(crate::from_slice::<BoolArray<5>>(&bytes).unwrap(), array);
+
+        // Bytes other than 0 and 1 are rejected like any other bool.
+        assert_eq!(
+            crate::from_slice::<BoolArray<5>>(&[1, 0, 2, 1, 1]).err(),
+            Some(crate::Error::At { position: 2, error: Box::new(crate::Error::Overflow) }),
+        );
+        assert!(crate::from_slice::<BoolArray<5>>(&bytes[..4]).is_err());
+    }
+}
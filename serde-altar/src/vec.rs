@@ -4,8 +4,217 @@ pub struct VecI16Flags (pub Vec<bool>);
 /// A ULEB128-sized [Vec] serialized as a sequence of `T`.
 pub struct VecULEB128<T> (pub Vec<T>);
 
-/// A [i16]-sized [Vec] serialized as a sequence of `T`.
-pub struct VecI16<T> (pub Vec<T>);
+/// A SLEB128-sized [Vec] serialized as a sequence of `T`.
+pub struct VecSLEB128<T> (pub Vec<T>);
 
-/// A [i32]-sized [Vec] serialized as a sequence of `T`.
-pub struct VecI32<T> (pub Vec<T>);
+/// A ULEB128-sized table of `K` keys and `V` values, serialized as a sequence of alternating keys and values.
+///
+/// Entries are kept in the order they are stored in, and keys may be repeated.
+pub struct MapULEB128<K, V> (pub Vec<(K, V)>);
+
+/// An [i32]-sized [Vec] serialized as a sequence of runs of equal `T`s, each run being a `T` followed by the amount of times it's repeated as a `C`.
+///
+/// `C` is the integer type of the repeat counts, either [u8] or [u16]; runs longer than it allows are split into multiple runs.
+pub struct RleVec<T, C> (pub Vec<T>, pub std::marker::PhantomData<C>);
+
+impl<T, C> RleVec<T, C> where C: RleCount {
+    /// Wrap the given expanded `values`.
+    pub fn new(values: Vec<T>) -> Self {
+        Self(values, std::marker::PhantomData)
+    }
+}
+
+/// An integer type the repeat counts of a [RleVec] can be stored as.
+pub trait RleCount: Copy + Into<usize> + TryFrom<usize> + serde::ser::Serialize + for<'de> serde::de::Deserialize<'de> {
+    /// The largest repeat count which can be stored.
+    const MAX: usize;
+}
+
+impl RleCount for u8 {
+    const MAX: usize = u8::MAX as usize;
+}
+
+impl RleCount for u16 {
+    const MAX: usize = u16::MAX as usize;
+}
+
+impl_framed_vec!(VecI16, VecI16Visitor, i16, crate::de::SEQ_I16, hooks(serialize_vec_i16, deserialize_vec_i16, visit_vec_i16));
+impl_framed_vec!(VecI32, VecI32Visitor, i32, crate::de::SEQ_I32, hooks(serialize_vec_i32, deserialize_vec_i32, visit_vec_i32));
+impl_framed_vec!(VecU8, VecU8Visitor, u8, crate::de::SEQ_U8, hooks(serialize_vec_u8, deserialize_vec_u8, visit_vec_u8));
+impl_framed_vec!(VecU32, VecU32Visitor, u32, crate::de::SEQ_U32, hooks(serialize_vec_u32, deserialize_vec_u32, visit_vec_u32));
+impl_framed_vec!(VecI64, VecI64Visitor, i64, crate::de::SEQ_I64, hooks(serialize_vec_i64, deserialize_vec_i64, visit_vec_i64));
+
+/// A [i32]-sized [Vec] of [f32]s, serialized like a [VecI32] of [f32]s but deserialized all at once instead of element by element.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VecI32F32 (pub Vec<f32>);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_vec_uleb128_roundtrip() {
+        let inner = vec![vec![], vec![1], vec![2; 127], vec![3; 128], vec![4; 1000]];
+        let nested = VecULEB128(inner.iter().cloned().map(VecULEB128).collect());
+        let bytes = crate::to_bytes(nested).unwrap();
+        assert_eq!(bytes.len(), 1 + 1 + (1 + 1) + (1 + 127) + (2 + 128) + (2 + 1000));
+
+        let decoded: VecULEB128<VecULEB128<u8>> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0.into_iter().map(|vec| vec.0).collect::<Vec<_>>(), inner);
+
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let decoded = <VecULEB128<VecULEB128<u8>> as crate::Deserialize<VecULEB128<u8>>>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded.0.into_iter().map(|vec| vec.0).collect::<Vec<_>>(), inner);
+        assert!(de.remaining().is_empty());
+    }
+
+    macro_rules! test_framed_vec {
+        ($test:ident, $name:ident, $prefix:ty, $element:ty) => {
+            #[test]
+            fn $test() {
+                let values: Vec<$element> = vec![1, 2, 3, <$element>::MAX];
+                let bytes = crate::to_bytes($name(values.clone())).unwrap();
+                assert_eq!(bytes[..std::mem::size_of::<$prefix>()], (4 as $prefix).to_le_bytes());
+                assert_eq!(bytes.len(), std::mem::size_of::<$prefix>() + 4 * std::mem::size_of::<$element>());
+
+                let decoded: $name<$element> = crate::from_slice(&bytes).unwrap();
+                assert_eq!(decoded.0, values);
+
+                let mut de = crate::SliceDeserializer::from_slice(&bytes);
+                let decoded = <$name<$element> as crate::Deserialize<$element>>::deserialize(&mut de).unwrap();
+                assert_eq!(decoded.0, values);
+                assert!(de.remaining().is_empty());
+
+                assert!(crate::from_slice::<$name<$element>>(&bytes[..bytes.len() - 1]).is_err());
+            }
+        };
+    }
+
+    test_framed_vec!(vec_i16_roundtrip, VecI16, i16, i16);
+    test_framed_vec!(vec_i32_roundtrip, VecI32, i32, u16);
+    test_framed_vec!(vec_u8_roundtrip, VecU8, u8, u8);
+    test_framed_vec!(vec_u32_roundtrip, VecU32, u32, i32);
+    test_framed_vec!(vec_i64_roundtrip, VecI64, i64, u64);
+
+    #[test]
+    fn vec_uleb128_long_roundtrip() {
+        let values: Vec<i32> = (0..200).map(|value| value * -3).collect();
+        let bytes = crate::to_bytes(VecULEB128(values.clone())).unwrap();
+        assert_eq!(bytes[..2], [0xC8, 0x01]);
+        assert_eq!(bytes.len(), 2 + 200 * 4);
+
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let decoded = <VecULEB128<i32> as crate::Deserialize<i32>>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded.0, values);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn vec_i16_flags_roundtrip() {
+        let flags: Vec<bool> = (0..13).map(|index| index % 3 == 0).collect();
+        let bytes = crate::to_bytes(VecI16Flags(flags.clone())).unwrap();
+        assert_eq!(bytes, [13, 0, 0b0100_1001, 0b0001_0010]);
+
+        let decoded: VecI16Flags = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0, flags);
+
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let decoded = <VecI16Flags as crate::Deserialize<bool>>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded.0, flags);
+        assert!(de.remaining().is_empty());
+    }
+
+    #[test]
+    fn vec_i32_f32_matches_element_wise() {
+        let mut values: Vec<f32> = (0..100_000).map(|index| f32::from_bits((index as u32).wrapping_mul(2_654_435_761))).collect();
+        values[..5].copy_from_slice(&[0.0, -0.0, f32::INFINITY, f32::NEG_INFINITY, f32::MIN_POSITIVE]);
+        let bytes = crate::to_bytes(VecI32F32(values.clone())).unwrap();
+        assert_eq!(bytes.len(), 4 + 100_000 * 4);
+
+        let bulk: VecI32F32 = crate::from_slice(&bytes).unwrap();
+        let element_wise: VecI32<f32> = crate::from_slice(&bytes).unwrap();
+        let bits = |values: &[f32]| values.iter().map(|value| value.to_bits()).collect::<Vec<u32>>();
+        assert_eq!(bits(&bulk.0), bits(&values));
+        assert_eq!(bits(&element_wise.0), bits(&values));
+    }
+
+    #[test]
+    fn vec_i32_f32_truncated() {
+        let bytes = crate::to_bytes(VecI32F32(vec![1.0, 2.0])).unwrap();
+        assert!(crate::from_slice::<VecI32F32>(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn vec_i16_flags_truncated() {
+        // 20 flags take 3 bytes, but only one is there.
+        let bytes = [20, 0, 0xFF];
+        let expected = Some(crate::Error::At { position: 2, error: Box::new(crate::Error::Truncated { requested: 3, remaining: 1 }) });
+        assert_eq!(crate::from_slice::<VecI16Flags>(&bytes).err(), expected);
+
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        assert_eq!(<VecI16Flags as crate::Deserialize<bool>>::deserialize(&mut de).err(), expected);
+        assert_eq!(de.remaining(), [0xFF]);
+
+        // Readers can't tell how much they have left, so they fail once they run out.
+        let mut reader = &bytes[..];
+        let result = crate::from_reader::<_, VecI16Flags>(&mut reader);
+        assert_eq!(result.err(), Some(crate::Error::At { position: 2, error: Box::new(crate::Error::Eof) }));
+    }
+
+    #[test]
+    fn framed_vec_allocation_limit() {
+        // A million i64s take 8 MB, which is checked before any of them is read.
+        let bytes = 1_000_000_i32.to_le_bytes();
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).max_alloc(1024);
+        let result = <VecI32<i64> as crate::Deserialize<i64>>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::AllocationLimit { requested: 8_000_000, limit: 1024 }));
+
+        let mut de = crate::SliceDeserializer::from_slice(&[0xC0, 0x84, 0x3D]).max_alloc(1024);
+        let result = <VecULEB128<u32> as crate::Deserialize<u32>>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::AllocationLimit { requested: 4_000_000, limit: 1024 }));
+
+        // Tables are limited by the size of their entries.
+        let mut de = crate::SliceDeserializer::from_slice(&[0xC0, 0x84, 0x3D]).max_alloc(1024);
+        let result = <MapULEB128<u32, u16> as crate::Deserialize<u32>>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::AllocationLimit { requested: 8_000_000, limit: 1024 }));
+
+        // Lengths within the limit are read as usual.
+        let mut bytes = 2_i16.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[1, 0, 2, 0]);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes).max_alloc(4);
+        let vec = <VecI16<i16> as crate::Deserialize<i16>>::deserialize(&mut de).unwrap();
+        assert_eq!(vec.0, [1, 2]);
+    }
+
+    #[test]
+    fn framed_vec_bogus_length() {
+        // Without any elements to back it, a huge length fails as soon as the input ends.
+        let bytes = i32::MAX.to_le_bytes();
+        let result = crate::from_slice::<VecI32<u64>>(&bytes);
+        assert!(matches!(result, Err(crate::Error::At { position: 4, .. })));
+    }
+
+    #[test]
+    fn rle_vec_allocation_limit() {
+        // A single run claiming to expand to i32::MAX values.
+        let mut bytes = i32::MAX.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[7, 0xFF, 0xFF]);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let result = <RleVec<u16, u16> as crate::Deserialize<u16>>::deserialize(&mut de);
+        assert_eq!(result.err(), Some(crate::Error::AllocationLimit { requested: 2 * i32::MAX as usize, limit: crate::DEFAULT_MAX_ALLOC }));
+    }
+
+    #[test]
+    fn rle_vec_run_past_len() {
+        // Three values are stored, but the only run expands to four.
+        let mut bytes = 3_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[7, 0, 3]);
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        assert!(<RleVec<u16, u8> as crate::Deserialize<u16>>::deserialize(&mut de).is_err());
+
+        bytes[0] = 4;
+        let mut de = crate::SliceDeserializer::from_slice(&bytes);
+        let decoded = <RleVec<u16, u8> as crate::Deserialize<u16>>::deserialize(&mut de).unwrap();
+        assert_eq!(decoded.0, [7; 4]);
+    }
+}
@@ -0,0 +1,1028 @@
+use crate::VecI32;
+
+/// Bits of a tile header byte storing the [TileShape] of the tile.
+pub(crate) const TILE_SHAPE_MASK: u8 = 0b0111_0000;
+
+/// Amount of bits to shift a tile header byte by to obtain its [TileShape].
+pub(crate) const TILE_SHAPE_SHIFT: u8 = 4;
+
+/// The ids of the [CreativePower]s set through a slider, whose value is stored as a [f32]; all the other powers are stored as a `bool`.
+///
+/// The powers are, in order: time speed and enemy difficulty.
+pub const CREATIVE_POWER_SLIDERS: [i16; 2] = [8, 12];
+
+/// The power id terminating [CreativePowers].
+pub const CREATIVE_POWERS_END: i16 = -1;
+
+/// The world version in which each of the [BossFlags] was introduced, in file order.
+///
+/// The flags are, in order: Eye of Cthulhu, Eater of Worlds or Brain of Cthulhu, Skeletron, Queen Bee, The Destroyer, The Twins, Skeletron Prime, any mechanical boss, Plantera, Golem and King Slime.
+pub const BOSS_FLAGS_VERSIONS: [i32; 11] = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 118];
+
+/// The "boss defeated" flags of the world header, serialized as a sequence of `bool`s whose length depends on the world version.
+///
+/// Only the flags present in the file are stored; the ones introduced in later versions can be accessed through [BossFlags::get].
+pub struct BossFlags(pub Vec<bool>);
+
+impl BossFlags {
+    /// The amount of flags stored in a world file of the given `version`.
+    pub fn count_for_version(version: i32) -> usize {
+        BOSS_FLAGS_VERSIONS.iter().filter(|introduced| **introduced <= version).count()
+    }
+
+    /// Get the flag at the given `index`, defaulting to `false` if it is not stored in the file.
+    pub fn get(&self, index: usize) -> bool {
+        self.0.get(index).copied().unwrap_or(false)
+    }
+}
+
+/// The tile types chosen for each ore tier when the world was generated, stored in the world header as consecutive [i32]s.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OreTiers {
+    /// Copper or Tin.
+    pub copper: i32,
+
+    /// Iron or Lead.
+    pub iron: i32,
+
+    /// Silver or Tungsten.
+    pub silver: i32,
+
+    /// Gold or Platinum.
+    pub gold: i32,
+
+    /// Cobalt or Palladium.
+    pub cobalt: i32,
+
+    /// Mythril or Orichalcum.
+    pub mythril: i32,
+
+    /// Adamantite or Titanium.
+    pub adamantite: i32,
+}
+
+/// The state of the party event, stored in the world file after the town NPCs.
+#[derive(serde::Serialize, serde::Deserialize)]
+pub struct PartyData {
+    /// Whether a party was started manually, by using a Party Center.
+    pub manual: bool,
+
+    /// Whether a party started naturally.
+    pub genuine: bool,
+
+    /// The amount of ticks before another party can start naturally.
+    pub cooldown: i32,
+
+    /// The ids of the NPCs celebrating the party.
+    pub celebrating_npcs: VecI32<i32>,
+}
+
+/// The item ids sold by a shop, stored as `N` consecutive [i32]s, where `0` is an empty slot.
+pub struct ShopInventory<const N: usize> (pub [i32; N]);
+
+/// The inventory of the Traveling Merchant.
+pub type TravelingMerchantInventory = ShopInventory<40>;
+
+impl<const N: usize> ShopInventory<N> {
+    /// Iterate over the index and the item id of the slots which are not empty.
+    pub fn occupied_slots(&self) -> impl Iterator<Item = (usize, i32)> + '_ {
+        self.0.iter().copied().enumerate().filter(|(_index, id)| *id != 0)
+    }
+}
+
+/// The appearance and phase of the moon, stored in the world header.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MoonData {
+    /// The texture used to draw the moon.
+    pub moon_type: u8,
+
+    /// The current phase of the moon, from `0` (full moon) to `7`.
+    pub moon_phase: i32,
+}
+
+/// The state of invasions and related timers, stored in the world header.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct InvasionData {
+    /// The type of the ongoing invasion, or `0` if there is none.
+    pub invasion_type: i32,
+
+    /// The amount of enemies left to defeat to end the invasion.
+    pub invasion_size: i32,
+
+    /// The horizontal position the invasion is approaching from.
+    pub invasion_x: f64,
+
+    /// The amount of ticks left before the slime rain ends.
+    pub slime_rain_time: f64,
+
+    /// The amount of days before the Enchanted Sundial can be used again.
+    pub sundial_cooldown: u8,
+}
+
+/// How the world was generated, stored in the world header right after its name.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct GenInfo {
+    /// The seed the world was generated with, as it was typed in.
+    pub seed: String,
+
+    /// The version of the world generator, packed in an [u64].
+    pub generator_version: u64,
+}
+
+/// The shape of a tile, stored in three bits of one of the tile header bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileShape {
+    /// A full block.
+    Normal,
+
+    /// The bottom half of a block.
+    HalfBrick,
+
+    /// A block with its top right corner cut off.
+    SlopeTR,
+
+    /// A block with its top left corner cut off.
+    SlopeTL,
+
+    /// A block with its bottom right corner cut off.
+    SlopeBR,
+
+    /// A block with its bottom left corner cut off.
+    SlopeBL,
+}
+
+impl TileShape {
+    /// Get the shape with the given value, as stored in the file, if it is valid.
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(TileShape::Normal),
+            1 => Some(TileShape::HalfBrick),
+            2 => Some(TileShape::SlopeTR),
+            3 => Some(TileShape::SlopeTL),
+            4 => Some(TileShape::SlopeBR),
+            5 => Some(TileShape::SlopeBL),
+            _ => None,
+        }
+    }
+
+    /// The value of the shape, as stored in the file.
+    pub fn value(self) -> u8 {
+        match self {
+            TileShape::Normal    => 0,
+            TileShape::HalfBrick => 1,
+            TileShape::SlopeTR   => 2,
+            TileShape::SlopeTL   => 3,
+            TileShape::SlopeBR   => 4,
+            TileShape::SlopeBL   => 5,
+        }
+    }
+
+    /// Decode the shape from the bits of a tile `header` byte, if they are valid.
+    pub fn from_header(header: u8) -> Option<Self> {
+        Self::from_value((header & TILE_SHAPE_MASK) >> TILE_SHAPE_SHIFT)
+    }
+
+    /// Encode the shape into the bits of a tile `header` byte, leaving the other bits untouched.
+    pub fn to_header(self, header: u8) -> u8 {
+        (header & !TILE_SHAPE_MASK) | (self.value() << TILE_SHAPE_SHIFT)
+    }
+}
+
+/// A town NPC and the house it lives in, stored in the NPC section of the world file.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TownNpc {
+    /// The name given to the NPC.
+    pub name: String,
+
+    /// The id of the NPC.
+    pub npc_id: i32,
+
+    /// Whether the NPC has no house.
+    pub homeless: bool,
+
+    /// The horizontal tile coordinate of the house of the NPC.
+    pub home_x: i32,
+
+    /// The vertical tile coordinate of the house of the NPC.
+    pub home_y: i32,
+
+    /// The bits selecting the variation of the NPC, such as its shimmered appearance.
+    pub variation_flags: u8,
+}
+
+/// The state of a Journey mode power, serialized as its [i16] id followed by its value, whose type depends on the id.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CreativePower {
+    /// A power which is either enabled or disabled.
+    Toggle {
+        /// The id of the power.
+        id: i16,
+
+        /// Whether the power is enabled.
+        enabled: bool,
+    },
+
+    /// A power set through a slider, listed in [CREATIVE_POWER_SLIDERS].
+    Slider {
+        /// The id of the power.
+        id: i16,
+
+        /// The position of the slider, from `0.0` to `1.0`.
+        value: f32,
+    },
+}
+
+impl CreativePower {
+    /// Whether the power with the given `id` is set through a slider.
+    pub fn is_slider(id: i16) -> bool {
+        CREATIVE_POWER_SLIDERS.contains(&id)
+    }
+
+    /// The id of the power.
+    pub fn id(&self) -> i16 {
+        match self {
+            CreativePower::Toggle { id, .. } => *id,
+            CreativePower::Slider { id, .. } => *id,
+        }
+    }
+}
+
+/// The Journey mode powers saved in the world file, serialized one after the other and terminated by [CREATIVE_POWERS_END].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CreativePowers (pub Vec<CreativePower>);
+
+/// The amount of tiles of each type in the world, stored as `N` consecutive [i32]s indexed by tile type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TileCounts<const N: usize> (pub Vec<i32>);
+
+impl<const N: usize> TileCounts<N> {
+    /// Get the tile type with the highest count along with its count, preferring the lowest type on ties.
+    pub fn most_common(&self) -> Option<(usize, i32)> {
+        self.0.iter().copied().enumerate().fold(None, |best, (tile_type, count)| match best {
+            Some((_, best_count)) if best_count >= count => best,
+            _ => Some((tile_type, count)),
+        })
+    }
+}
+
+/// The time of day and the events tied to it, stored in the world header.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct TimeState {
+    /// Whether it is currently day.
+    pub is_day: bool,
+
+    /// The amount of ticks elapsed since the start of the current day or night.
+    pub time: f64,
+
+    /// The current phase of the moon, from `0` (full moon) to `7`.
+    pub moon_phase: i32,
+
+    /// Whether a Blood Moon is happening.
+    pub blood_moon: bool,
+
+    /// Whether a Solar Eclipse is happening.
+    pub eclipse: bool,
+}
+
+/// The payload of a logic sensor tile entity, stored after the tile entity position.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LogicSensor {
+    /// The condition checked by the sensor, such as day, night or player above.
+    pub logic_check: u8,
+
+    /// Whether the sensor is currently active.
+    pub on: bool,
+}
+
+/// The payload of a weighted pressure plate tile entity, stored after the tile entity position.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WeightedPlate {
+    /// Whether the plate is currently pressed.
+    pub on: bool,
+}
+
+/// The name strings stored in sequence in the world header.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorldNames {
+    /// The name of the world, as chosen by the player.
+    pub name: String,
+
+    /// The text the world seed was generated from, which may be empty.
+    pub seed_text: String,
+}
+
+/// The amount of [i32]s making up [Backgrounds].
+pub(crate) const BACKGROUNDS_LEN: usize = 3 + 4 + 3 + 4;
+
+/// The background styles of the world header, stored as consecutive [i32]s and read all at once.
+///
+/// Backgrounds change style at given x coordinates, so each list of boundaries is followed by the styles of the regions between them.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct Backgrounds {
+    /// The x coordinates at which the tree background changes.
+    pub tree_x: [i32; 3],
+
+    /// The styles of the tree background in each region.
+    pub tree_styles: [i32; 4],
+
+    /// The x coordinates at which the cave background changes.
+    pub cave_x: [i32; 3],
+
+    /// The styles of the cave background in each region.
+    pub cave_styles: [i32; 4],
+}
+
+/// The world version from which [Difficulty] is stored as a game mode [i32] instead of an expert mode `bool`.
+pub const GAME_MODE_VERSION: i32 = 209;
+
+/// The difficulty of the world, stored in the world header in a version-dependent form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Difficulty {
+    /// Whether the world is in expert mode, before [GAME_MODE_VERSION].
+    Expert(bool),
+
+    /// The game mode of the world, since [GAME_MODE_VERSION]: `0` for classic, `1` for expert, `2` for master and `3` for journey.
+    GameMode(i32),
+}
+
+impl Difficulty {
+    /// Whether enemies have expert mode behaviour, which is also the case in master mode.
+    pub fn is_expert(&self) -> bool {
+        match self {
+            Difficulty::Expert(expert) => *expert,
+            Difficulty::GameMode(mode) => *mode == 1 || *mode == 2,
+        }
+    }
+
+    /// Whether the world is in master mode.
+    pub fn is_master(&self) -> bool {
+        matches!(self, Difficulty::GameMode(2))
+    }
+}
+
+/// The depths of the world layers and the spawn point, stored in the world header.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LayerDepths {
+    /// The y coordinate at which the surface layer ends, in tiles.
+    pub world_surface: f64,
+
+    /// The y coordinate at which the cavern layer starts, in tiles.
+    pub rock_layer: f64,
+
+    /// The x coordinate of the spawn point, in tiles.
+    pub spawn_tile_x: i32,
+
+    /// The y coordinate of the spawn point, in tiles.
+    pub spawn_tile_y: i32,
+}
+
+/// The kill counts of mobs identified by their name, serialized as its [i32] length followed by each name and its [i32] count.
+///
+/// Entries are kept in the order they are stored in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NamedKillCounts (pub Vec<(String, i32)>);
+
+impl NamedKillCounts {
+    /// The kill count of the mob with the given name, if it has one.
+    pub fn get(&self, name: &str) -> Option<i32> {
+        self.0.iter().find(|(key, _count)| key == name).map(|(_key, count)| *count)
+    }
+}
+
+/// The integrity fields of the world footer, repeating the revision and the favorite flags of the header.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WorldFooter {
+    /// The amount of times the file was saved.
+    pub revision: u32,
+
+    /// The favorite flags of the file, whose lowest bit tells whether it is a favorite.
+    pub favorites: u64,
+}
+
+impl WorldFooter {
+    /// Check that the footer repeats the given header values.
+    ///
+    /// Fails with [crate::Error::FooterMismatch] naming the first field which differs.
+    pub fn verify(&self, revision: u32, favorites: u64) -> crate::Result<()> {
+        if self.revision != revision {
+            return Err(crate::Error::FooterMismatch { field: "revision", header: revision.into(), footer: self.revision.into() })
+        }
+        if self.favorites != favorites {
+            return Err(crate::Error::FooterMismatch { field: "favorites", header: favorites, footer: self.favorites })
+        }
+        Ok(())
+    }
+}
+
+/// Names of the paint colors, indexed by their id.
+const PAINT_COLOR_NAMES: [&str; 32] = [
+    "None", "Red", "Orange", "Yellow", "Lime", "Green", "Teal", "Cyan",
+    "Sky Blue", "Blue", "Purple", "Violet", "Pink", "Deep Red", "Deep Orange", "Deep Yellow",
+    "Deep Lime", "Deep Green", "Deep Teal", "Deep Cyan", "Deep Sky Blue", "Deep Blue", "Deep Purple", "Deep Violet",
+    "Deep Pink", "Black", "White", "Gray", "Brown", "Shadow", "Negative", "Illuminant",
+];
+
+/// The paint applied to a tile or a wall, stored as its color id in a single [u8].
+///
+/// Ids go from `0`, meaning no paint, to [PaintColor::MAX]; any other id is rejected.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaintColor (pub u8);
+
+impl PaintColor {
+    /// The highest valid paint color id.
+    pub const MAX: u8 = PAINT_COLOR_NAMES.len() as u8 - 1;
+
+    /// Get the paint color with the given id, if it is valid.
+    pub fn new(id: u8) -> Option<Self> {
+        match id <= Self::MAX {
+            true => Some(PaintColor(id)),
+            false => None,
+        }
+    }
+
+    /// The name of the paint color as displayed in game, if its id is valid.
+    pub fn name(self) -> Option<&'static str> {
+        PAINT_COLOR_NAMES.get(self.0 as usize).copied()
+    }
+}
+
+/// A stack of items, as stored by the tile entities displaying an item.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ItemStack {
+    /// The type of the item, or `0` if there is none.
+    pub id: i16,
+
+    /// The prefix (modifier) of the item, or `0` if it has none.
+    pub prefix: u8,
+
+    /// The amount of items in the stack.
+    pub stack: i16,
+}
+
+/// The payload of an item frame tile entity, stored after the tile entity position.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ItemFrame {
+    /// The item displayed in the frame.
+    pub item: ItemStack,
+}
+
+/// The payload of a weapon rack tile entity, stored after the tile entity position.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct WeaponRack {
+    /// The item displayed on the rack.
+    pub item: ItemStack,
+}
+
+/// The world version from which [EventTimers] contain the [Sandstorm] state.
+pub const SANDSTORM_VERSION: i32 = 174;
+
+/// The world version from which [EventTimers] contain the [LanternNight] state.
+pub const LANTERN_NIGHT_VERSION: i32 = 228;
+
+/// The state of the desert sandstorm, stored in the world header.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Sandstorm {
+    /// Whether a sandstorm is happening.
+    pub happening: bool,
+
+    /// The amount of ticks left before the sandstorm ends.
+    pub time_left: i32,
+
+    /// The current strength of the sandstorm.
+    pub severity: f32,
+
+    /// The strength the sandstorm is moving towards.
+    pub intended_severity: f32,
+}
+
+/// The state of the Lantern Night event, stored in the world header.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LanternNight {
+    /// The amount of days left before a Lantern Night can happen naturally.
+    pub cooldown: i32,
+
+    /// Whether a natural Lantern Night is happening.
+    pub genuine: bool,
+
+    /// Whether a Lantern Night was started by the player.
+    pub manual: bool,
+
+    /// Whether the next night will be a Lantern Night.
+    pub next_night: bool,
+}
+
+/// The event timers of the world header, whose presence depends on the world version.
+///
+/// Only the groups present in the file are stored; the missing ones can be accessed with their default values through [EventTimers::sandstorm] and [EventTimers::lantern_night].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventTimers {
+    /// The sandstorm state, since [SANDSTORM_VERSION].
+    pub sandstorm: Option<Sandstorm>,
+
+    /// The Lantern Night state, since [LANTERN_NIGHT_VERSION].
+    pub lantern_night: Option<LanternNight>,
+}
+
+impl EventTimers {
+    /// The sandstorm state, defaulting to no sandstorm if it is not stored in the file.
+    pub fn sandstorm(&self) -> Sandstorm {
+        self.sandstorm.clone().unwrap_or_default()
+    }
+
+    /// The Lantern Night state, defaulting to no Lantern Night if it is not stored in the file.
+    pub fn lantern_night(&self) -> LanternNight {
+        self.lantern_night.clone().unwrap_or_default()
+    }
+}
+
+/// The world version from which [ShimmerData] is appended to the world header.
+pub const SHIMMER_VERSION: i32 = 269;
+
+/// The position of the shimmer lake, in tiles.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ShimmerPosition {
+    /// The horizontal coordinate of the lake.
+    pub x: i32,
+
+    /// The vertical coordinate of the lake.
+    pub y: i32,
+}
+
+/// The shimmer fields appended to the world header since [SHIMMER_VERSION], which are missing from older files.
+///
+/// Nothing is read or written for files older than [SHIMMER_VERSION]; [ShimmerData::position] then defaults to the origin.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ShimmerData (pub Option<ShimmerPosition>);
+
+impl ShimmerData {
+    /// The position of the shimmer lake, defaulting to the origin if it is not stored in the file.
+    pub fn position(&self) -> ShimmerPosition {
+        self.0.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SliceDeserializer;
+
+    /// Deserialize a `T` out of all of `bytes`, as stored in a file of the given `version`.
+    fn from_versioned<'de, T>(bytes: &'de [u8], version: i32) -> crate::Result<T> where T: serde::de::Deserialize<'de> {
+        let mut de = SliceDeserializer::from_slice(bytes).with_version(version);
+        let value = T::deserialize(&mut de)?;
+        assert!(de.remaining().is_empty());
+        Ok(value)
+    }
+
+    #[test]
+    fn boss_flags_old_version() {
+        let bytes = crate::to_bytes(BossFlags(vec![true; 10])).unwrap();
+        assert_eq!(bytes.len(), 10);
+        let flags: BossFlags = from_versioned(&bytes, 117).unwrap();
+        assert_eq!(flags.0.len(), 10);
+        assert!(flags.get(9));
+        // King Slime was introduced later, so it defaults to not defeated.
+        assert!(!flags.get(10));
+    }
+
+    #[test]
+    fn boss_flags_new_version() {
+        let mut stored = vec![false; 11];
+        stored[10] = true;
+        let bytes = crate::to_bytes(BossFlags(stored)).unwrap();
+        let flags: BossFlags = from_versioned(&bytes, 118).unwrap();
+        assert_eq!(flags.0.len(), 11);
+        assert!(!flags.get(0));
+        assert!(flags.get(10));
+    }
+
+    #[test]
+    fn boss_flags_missing_version() {
+        let bytes = [0; 11];
+        let result: crate::Result<BossFlags> = crate::from_slice(&bytes);
+        assert_eq!(result.err(), Some(crate::Error::MissingVersion));
+    }
+
+    #[test]
+    fn ore_tiers_decode() {
+        let mut bytes = vec![];
+        for tile in [7_i32, 166, 9, 169, 221, 222, 223] {
+            bytes.extend_from_slice(&tile.to_le_bytes());
+        }
+        let tiers: OreTiers = crate::from_slice(&bytes).unwrap();
+        assert_eq!(tiers.iron, 166);
+        assert_eq!(tiers.adamantite, 223);
+        assert_eq!(crate::to_bytes(tiers).unwrap(), bytes);
+    }
+
+    #[test]
+    fn party_data_roundtrip() {
+        let party = PartyData { manual: false, genuine: true, cooldown: 3600, celebrating_npcs: VecI32(vec![17, 22, 208]) };
+        let bytes = crate::to_bytes(party).unwrap();
+        assert_eq!(bytes.len(), 1 + 1 + 4 + 4 + 3 * 4);
+        assert_eq!(&bytes[..2], [0, 1]);
+        let decoded: PartyData = crate::from_slice(&bytes).unwrap();
+        assert!(!decoded.manual);
+        assert!(decoded.genuine);
+        assert_eq!(decoded.cooldown, 3600);
+        assert_eq!(decoded.celebrating_npcs.0, [17, 22, 208]);
+    }
+
+    #[test]
+    fn party_data_invalid_bool() {
+        let mut bytes = crate::to_bytes(PartyData { manual: true, genuine: false, cooldown: 0, celebrating_npcs: VecI32(vec![]) }).unwrap();
+        bytes[1] = 2;
+        let result: crate::Result<PartyData> = crate::from_slice(&bytes);
+        assert_eq!(result.err(), Some(crate::Error::At { position: 1, error: Box::new(crate::Error::Overflow) }));
+    }
+
+    #[test]
+    fn shop_inventory_occupied_slots() {
+        let mut slots = [0; 40];
+        slots[0] = 3;
+        slots[17] = 4956;
+        slots[39] = -1;
+        let bytes = crate::to_bytes(ShopInventory(slots)).unwrap();
+        assert_eq!(bytes.len(), 40 * 4);
+        let inventory: TravelingMerchantInventory = crate::from_slice(&bytes).unwrap();
+        assert_eq!(inventory.0, slots);
+        assert_eq!(inventory.occupied_slots().count(), 3);
+        assert_eq!(inventory.occupied_slots().nth(1), Some((17, 4956)));
+    }
+
+    #[test]
+    fn shop_inventory_truncated() {
+        let bytes = [0; 39 * 4];
+        let result: crate::Result<TravelingMerchantInventory> = crate::from_slice(&bytes);
+        assert_eq!(result.err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Truncated { requested: 40 * 4, remaining: 39 * 4 }) }));
+    }
+
+    #[test]
+    fn moon_data_decode() {
+        let bytes = [2, 5, 0, 0, 0];
+        let moon: MoonData = crate::from_slice(&bytes).unwrap();
+        assert_eq!(moon, MoonData { moon_type: 2, moon_phase: 5 });
+        assert_eq!(crate::to_bytes(moon).unwrap(), bytes);
+    }
+
+    #[test]
+    fn invasion_data_decode() {
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&2_i32.to_le_bytes());
+        bytes.extend_from_slice(&120_i32.to_le_bytes());
+        bytes.extend_from_slice(&4200.5_f64.to_le_bytes());
+        bytes.extend_from_slice(&0.0_f64.to_le_bytes());
+        bytes.push(3);
+        let invasion: InvasionData = crate::from_slice(&bytes).unwrap();
+        assert_eq!(invasion.invasion_type, 2);
+        assert_eq!(invasion.invasion_x, 4200.5);
+        assert_eq!(invasion.sundial_cooldown, 3);
+        assert_eq!(crate::to_bytes(invasion).unwrap(), bytes);
+    }
+
+    #[test]
+    fn gen_info_decode() {
+        let mut bytes = vec![0x05];
+        bytes.extend_from_slice(b"seed!");
+        bytes.extend_from_slice(&0x0001_0002_0003_0004_u64.to_le_bytes());
+        let decoded: GenInfo = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, GenInfo { seed: "seed!".to_string(), generator_version: 0x0001_0002_0003_0004 });
+        assert_eq!(crate::to_bytes(decoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn gen_info_truncated_version() {
+        let bytes = [0x01, b'a', 0x00, 0x00, 0x00];
+        assert!(crate::from_slice::<GenInfo>(&bytes).is_err());
+    }
+
+    #[test]
+    fn tile_shape_header_bits() {
+        let shapes = [
+            (0b0000_0000, TileShape::Normal),
+            (0b0001_0000, TileShape::HalfBrick),
+            (0b0010_0000, TileShape::SlopeTR),
+            (0b0011_0000, TileShape::SlopeTL),
+            (0b0100_0000, TileShape::SlopeBR),
+            (0b0101_0000, TileShape::SlopeBL),
+        ];
+        for (bits, shape) in shapes {
+            assert_eq!(TileShape::from_header(bits | 0b1000_1111), Some(shape));
+            assert_eq!(shape.to_header(0b1111_1111), bits | 0b1000_1111);
+            assert_eq!(crate::to_bytes(shape).unwrap(), [shape.value()]);
+            assert_eq!(crate::from_slice::<TileShape>(&[shape.value()]).unwrap(), shape);
+        }
+    }
+
+    #[test]
+    fn tile_shape_invalid_bits() {
+        assert_eq!(TileShape::from_header(0b0110_0000), None);
+        assert_eq!(TileShape::from_header(0b0111_0000), None);
+        assert!(crate::from_slice::<TileShape>(&[6]).is_err());
+    }
+
+    #[test]
+    fn town_npcs_decode() {
+        let mut bytes = 2_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[5, b'A', b'n', b'd', b'y', b'!']);
+        bytes.extend_from_slice(&22_i32.to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&100_i32.to_le_bytes());
+        bytes.extend_from_slice(&(-3_i32).to_le_bytes());
+        bytes.push(0b10);
+        bytes.extend_from_slice(&[0]);
+        bytes.extend_from_slice(&17_i32.to_le_bytes());
+        bytes.push(1);
+        bytes.extend_from_slice(&0_i32.to_le_bytes());
+        bytes.extend_from_slice(&0_i32.to_le_bytes());
+        bytes.push(0);
+
+        let npcs: VecI32<TownNpc> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(npcs.0, vec![
+            TownNpc { name: "Andy!".to_string(), npc_id: 22, homeless: false, home_x: 100, home_y: -3, variation_flags: 0b10 },
+            TownNpc { name: String::new(), npc_id: 17, homeless: true, home_x: 0, home_y: 0, variation_flags: 0 },
+        ]);
+        assert_eq!(crate::to_bytes(npcs).unwrap(), bytes);
+    }
+
+    #[test]
+    fn creative_powers_roundtrip() {
+        let powers = CreativePowers(vec![
+            CreativePower::Toggle { id: 0, enabled: true },
+            CreativePower::Slider { id: 8, value: 0.5 },
+        ]);
+        let bytes = crate::to_bytes(powers.clone()).unwrap();
+        let mut expected = vec![0, 0, 1, 8, 0];
+        expected.extend_from_slice(&0.5_f32.to_le_bytes());
+        expected.extend_from_slice(&CREATIVE_POWERS_END.to_le_bytes());
+        assert_eq!(bytes, expected);
+        assert_eq!(crate::from_slice::<CreativePowers>(&bytes).unwrap(), powers);
+    }
+
+    #[test]
+    fn creative_powers_unterminated() {
+        assert!(crate::from_slice::<CreativePowers>(&[0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn tile_counts_decode() {
+        let counts: Vec<i32> = (0..700).map(|tile_type| (tile_type * 37) % 691).collect();
+        let bytes: Vec<u8> = counts.iter().flat_map(|count| count.to_le_bytes()).collect();
+        let decoded: TileCounts<700> = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.0, counts);
+        assert_eq!(decoded.most_common(), Some((56, 690)));
+        assert_eq!(crate::to_bytes(decoded).unwrap(), bytes);
+
+        assert_eq!(TileCounts::<2>(vec![3, 3]).most_common(), Some((0, 3)));
+        assert_eq!(TileCounts::<0>(vec![]).most_common(), None);
+    }
+
+    #[test]
+    fn tile_counts_wrong_length() {
+        assert!(crate::from_slice::<TileCounts<700>>(&[0; 699 * 4]).is_err());
+        assert!(crate::to_bytes(TileCounts::<700>(vec![0; 699])).is_err());
+    }
+
+    #[test]
+    fn time_state_decode() {
+        let mut bytes = vec![1];
+        bytes.extend_from_slice(&13500.25_f64.to_le_bytes());
+        bytes.extend_from_slice(&4_i32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 1]);
+        let decoded: TimeState = crate::from_slice(&bytes).unwrap();
+        assert!(decoded.is_day);
+        assert_eq!(decoded.time, 13500.25);
+        assert_eq!(decoded.moon_phase, 4);
+        assert!(!decoded.blood_moon);
+        assert!(decoded.eclipse);
+        assert_eq!(crate::to_bytes(decoded).unwrap(), bytes);
+    }
+
+    #[test]
+    fn tile_entity_payloads_roundtrip() {
+        let sensor = LogicSensor { logic_check: 4, on: true };
+        let bytes = crate::to_bytes(sensor.clone()).unwrap();
+        assert_eq!(bytes, [4, 1]);
+        assert_eq!(crate::from_slice::<LogicSensor>(&bytes).unwrap(), sensor);
+
+        let plate = WeightedPlate { on: false };
+        let bytes = crate::to_bytes(plate.clone()).unwrap();
+        assert_eq!(bytes, [0]);
+        assert_eq!(crate::from_slice::<WeightedPlate>(&bytes).unwrap(), plate);
+    }
+
+    #[test]
+    fn tile_entity_payloads_invalid_bool() {
+        assert_eq!(crate::from_slice::<WeightedPlate>(&[2]).err(), Some(crate::Error::At { position: 0, error: Box::new(crate::Error::Overflow) }));
+        assert_eq!(crate::from_slice::<LogicSensor>(&[4]).err(), Some(crate::Error::At { position: 1, error: Box::new(crate::Error::Eof) }));
+    }
+
+    #[test]
+    fn world_names_roundtrip() {
+        let names = WorldNames { name: "Hallowed Grove".to_string(), seed_text: String::new() };
+        let bytes = crate::to_bytes(names.clone()).unwrap();
+        let mut expected = vec![14];
+        expected.extend_from_slice(b"Hallowed Grove");
+        expected.push(0);
+        assert_eq!(bytes, expected);
+        assert_eq!(crate::from_slice::<WorldNames>(&bytes).unwrap(), names);
+
+        let both_empty = WorldNames { name: String::new(), seed_text: String::new() };
+        assert_eq!(crate::from_slice::<WorldNames>(&[0, 0]).unwrap(), both_empty);
+    }
+
+    #[test]
+    fn backgrounds_decode() {
+        let values: Vec<i32> = (0..BACKGROUNDS_LEN as i32).map(|index| index * 100 - 1).collect();
+        let bytes: Vec<u8> = values.iter().flat_map(|value| value.to_le_bytes()).collect();
+        let decoded: Backgrounds = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded.tree_x, [-1, 99, 199]);
+        assert_eq!(decoded.cave_styles, [999, 1099, 1199, 1299]);
+        assert_eq!(crate::to_bytes(decoded).unwrap(), bytes);
+        assert!(crate::from_slice::<Backgrounds>(&bytes[..bytes.len() - 4]).is_err());
+    }
+
+    #[test]
+    fn difficulty_expert_bool_era() {
+        let expert: Difficulty = from_versioned(&[1], GAME_MODE_VERSION - 1).unwrap();
+        assert_eq!(expert, Difficulty::Expert(true));
+        assert!(expert.is_expert());
+        assert!(!expert.is_master());
+        assert_eq!(crate::to_bytes(expert).unwrap(), [1]);
+    }
+
+    #[test]
+    fn difficulty_game_mode_era() {
+        let master: Difficulty = from_versioned(&2_i32.to_le_bytes(), GAME_MODE_VERSION).unwrap();
+        assert_eq!(master, Difficulty::GameMode(2));
+        assert!(master.is_expert());
+        assert!(master.is_master());
+        assert_eq!(crate::to_bytes(master).unwrap(), 2_i32.to_le_bytes());
+
+        let journey: Difficulty = from_versioned(&3_i32.to_le_bytes(), GAME_MODE_VERSION + 50).unwrap();
+        assert!(!journey.is_expert());
+        assert_eq!(crate::from_slice::<Difficulty>(&[1]).err(), Some(crate::Error::MissingVersion));
+    }
+
+    #[test]
+    fn layer_depths_decode() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&350.0_f64.to_le_bytes());
+        bytes.extend_from_slice(&512.5_f64.to_le_bytes());
+        bytes.extend_from_slice(&2100_i32.to_le_bytes());
+        bytes.extend_from_slice(&(-1_i32).to_le_bytes());
+
+        let depths: LayerDepths = crate::from_slice(&bytes).unwrap();
+        assert_eq!(depths.world_surface, 350.0);
+        assert_eq!(depths.rock_layer, 512.5);
+        assert_eq!(depths.spawn_tile_x, 2100);
+        assert_eq!(depths.spawn_tile_y, -1);
+        assert_eq!(crate::to_bytes(depths).unwrap(), bytes);
+        assert!(crate::from_slice::<LayerDepths>(&bytes[..20]).is_err());
+    }
+
+    #[test]
+    fn named_kill_counts_round_trip() {
+        let counts = NamedKillCounts(vec![
+            ("Zombie".to_string(), 120),
+            ("Demon Eye".to_string(), 7),
+            ("Zombie".to_string(), -1),
+        ]);
+        let mut bytes = 3_i32.to_le_bytes().to_vec();
+        for (name, count) in &counts.0 {
+            bytes.push(name.len() as u8);
+            bytes.extend_from_slice(name.as_bytes());
+            bytes.extend_from_slice(&count.to_le_bytes());
+        }
+
+        assert_eq!(crate::to_bytes(counts.clone()).unwrap(), bytes);
+        let decoded: NamedKillCounts = crate::from_slice(&bytes).unwrap();
+        assert_eq!(decoded, counts);
+        assert_eq!(decoded.get("Demon Eye"), Some(7));
+        // The first of duplicate names wins.
+        assert_eq!(decoded.get("Zombie"), Some(120));
+        assert_eq!(decoded.get("Slime"), None);
+
+        assert!(crate::from_slice::<NamedKillCounts>(&(-1_i32).to_le_bytes()).is_err());
+        assert!(crate::from_slice::<NamedKillCounts>(&bytes[..bytes.len() - 4]).is_err());
+    }
+
+    #[test]
+    fn world_footer_verify() {
+        let mut bytes = 17_u32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&1_u64.to_le_bytes());
+        let footer: WorldFooter = crate::from_slice(&bytes).unwrap();
+        assert_eq!(footer, WorldFooter { revision: 17, favorites: 1 });
+        assert_eq!(crate::to_bytes(footer.clone()).unwrap(), bytes);
+        assert_eq!(footer.verify(17, 1), Ok(()));
+
+        let error = footer.verify(18, 1).unwrap_err();
+        assert_eq!(error, crate::Error::FooterMismatch { field: "revision", header: 18, footer: 17 });
+        assert_eq!(error.to_string(), "Footer revision should be 18 as in the header, but is 17");
+        assert_eq!(
+            footer.verify(17, 0).err(),
+            Some(crate::Error::FooterMismatch { field: "favorites", header: 0, footer: 1 }),
+        );
+    }
+
+    #[test]
+    fn paint_color_ids() {
+        let red: PaintColor = crate::from_slice(&[1]).unwrap();
+        assert_eq!(red, PaintColor(1));
+        assert_eq!(red.name(), Some("Red"));
+        assert_eq!(crate::to_bytes(red).unwrap(), [1]);
+
+        let illuminant: PaintColor = crate::from_slice(&[PaintColor::MAX]).unwrap();
+        assert_eq!(illuminant.name(), Some("Illuminant"));
+        assert_eq!(PaintColor::new(PaintColor::MAX + 1), None);
+        assert_eq!(PaintColor(PaintColor::MAX + 1).name(), None);
+
+        assert_eq!(
+            crate::from_slice::<PaintColor>(&[PaintColor::MAX + 1]).err(),
+            Some(crate::Error::Message("invalid value: integer `32`, expected a paint color between 0 and 31".to_string())),
+        );
+        assert!(crate::to_bytes(PaintColor(200)).is_err());
+    }
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    enum DisplayEntity {
+        Frame(ItemFrame),
+        Rack(WeaponRack),
+    }
+
+    impl_serialize_forward!(DisplayEntity);
+
+    #[test]
+    fn item_frame_and_weapon_rack_round_trip() {
+        let item = ItemStack { id: 757, prefix: 81, stack: 1 };
+        let bytes = [0xF5, 0x02, 81, 1, 0];
+
+        let frame: ItemFrame = crate::from_slice(&bytes).unwrap();
+        assert_eq!(frame, ItemFrame { item: item.clone() });
+        assert_eq!(crate::to_bytes(frame.clone()).unwrap(), bytes);
+        let rack: WeaponRack = crate::from_slice(&bytes).unwrap();
+        assert_eq!(rack.item, item);
+
+        // Nested payloads decode inside enum variants, after their discriminant.
+        let entity = DisplayEntity::Rack(WeaponRack { item: ItemStack { id: -3, prefix: 0, stack: 99 } });
+        let encoded = crate::to_bytes(entity).unwrap();
+        assert_eq!(encoded, [1, 0xFD, 0xFF, 0, 99, 0]);
+        assert_eq!(
+            crate::from_slice::<DisplayEntity>(&encoded).unwrap(),
+            DisplayEntity::Rack(WeaponRack { item: ItemStack { id: -3, prefix: 0, stack: 99 } }),
+        );
+        let mut framed = vec![0];
+        framed.extend_from_slice(&bytes);
+        assert_eq!(crate::from_slice::<DisplayEntity>(&framed).unwrap(), DisplayEntity::Frame(frame));
+        assert!(crate::from_slice::<DisplayEntity>(&framed[..5]).is_err());
+    }
+
+    #[test]
+    fn event_timers_new_version() {
+        let mut bytes = vec![1];
+        bytes.extend_from_slice(&3600_i32.to_le_bytes());
+        bytes.extend_from_slice(&0.5_f32.to_le_bytes());
+        bytes.extend_from_slice(&0.75_f32.to_le_bytes());
+        bytes.extend_from_slice(&2_i32.to_le_bytes());
+        bytes.extend_from_slice(&[0, 1, 0]);
+
+        let timers: EventTimers = from_versioned(&bytes, LANTERN_NIGHT_VERSION).unwrap();
+        assert_eq!(timers.sandstorm(), Sandstorm { happening: true, time_left: 3600, severity: 0.5, intended_severity: 0.75 });
+        assert_eq!(timers.lantern_night(), LanternNight { cooldown: 2, genuine: false, manual: true, next_night: false });
+        assert_eq!(crate::to_bytes(timers).unwrap(), bytes);
+
+        // Between the two versions only the sandstorm is stored.
+        let sandstorm_only: EventTimers = from_versioned(&bytes[..13], SANDSTORM_VERSION).unwrap();
+        assert!(sandstorm_only.sandstorm.is_some());
+        assert_eq!(sandstorm_only.lantern_night, None);
+        assert!(from_versioned::<EventTimers>(&bytes[..16], LANTERN_NIGHT_VERSION).is_err());
+    }
+
+    #[test]
+    fn event_timers_old_version() {
+        let timers: EventTimers = from_versioned(&[], SANDSTORM_VERSION - 1).unwrap();
+        assert_eq!(timers, EventTimers::default());
+        assert_eq!(timers.sandstorm(), Sandstorm::default());
+        assert_eq!(timers.lantern_night(), LanternNight::default());
+        assert_eq!(crate::to_bytes(timers).unwrap(), []);
+    }
+
+    #[test]
+    fn shimmer_data_new_version() {
+        let mut bytes = 1200_i32.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&650_i32.to_le_bytes());
+        let shimmer: ShimmerData = from_versioned(&bytes, SHIMMER_VERSION).unwrap();
+        assert_eq!(shimmer.position(), ShimmerPosition { x: 1200, y: 650 });
+        assert_eq!(crate::to_bytes(shimmer).unwrap(), bytes);
+        assert!(from_versioned::<ShimmerData>(&bytes[..4], SHIMMER_VERSION).is_err());
+    }
+
+    #[test]
+    fn shimmer_data_old_version() {
+        // Older files end right before the shimmer fields.
+        let shimmer: ShimmerData = from_versioned(&[], SHIMMER_VERSION - 1).unwrap();
+        assert_eq!(shimmer, ShimmerData(None));
+        assert_eq!(shimmer.position(), ShimmerPosition::default());
+        assert_eq!(crate::to_bytes(shimmer).unwrap(), []);
+    }
+}